@@ -0,0 +1,313 @@
+use crate::encoding::{self, Base64, Hex, ParseError, Strictness};
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitXor, BitXorAssign, Deref};
+
+/// An owned byte buffer that every cypher and attack in this crate can accept
+/// (via `AsRef<[u8]>`) or return, with conversions to and from the string
+/// encodings in `encoding`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Bytes(pub Vec<u8>);
+
+/// The textual representations `Bytes` can be displayed in
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Hex,
+    Base64,
+    /// printable ASCII as-is, everything else as `\n`, `\t`, `\r`, `\\` or `\xNN`
+    EscapedAscii,
+}
+
+/// Display adapter returned by `Bytes::display`
+pub struct Formatted<'a> {
+    bytes: &'a [u8],
+    format: Format,
+}
+
+impl Bytes {
+    pub fn new() -> Bytes {
+        Self(Vec::new())
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn to_hex(&self) -> Hex {
+        encoding::bytes2hex(&self.0)
+    }
+
+    pub fn to_base64(&self) -> Base64 {
+        Base64(base64::encode(&self.0))
+    }
+
+    /// interprets the bytes as UTF-8, replacing invalid sequences with `U+FFFD`
+    pub fn to_string_lossy(&self) -> String {
+        encoding::bytes2str(&self.0)
+    }
+
+    pub fn display(&self, format: Format) -> Formatted<'_> {
+        Formatted {
+            bytes: &self.0,
+            format,
+        }
+    }
+}
+
+/*****************
+ * CONVERSIONS
+ *****************/
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bs: Vec<u8>) -> Bytes {
+        Self(bs)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bs: &[u8]) -> Bytes {
+        Self(bs.to_vec())
+    }
+}
+
+/// takes the UTF-8 bytes of a string as-is (use `TryFrom<Hex>` or `TryFrom<Base64>` to decode)
+impl From<&str> for Bytes {
+    fn from(s: &str) -> Bytes {
+        Self(s.as_bytes().to_vec())
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Vec<u8> {
+        bytes.0
+    }
+}
+
+impl From<Bytes> for Hex {
+    fn from(bytes: Bytes) -> Hex {
+        bytes.to_hex()
+    }
+}
+
+impl From<Bytes> for Base64 {
+    fn from(bytes: Bytes) -> Base64 {
+        bytes.to_base64()
+    }
+}
+
+impl TryFrom<&Hex> for Bytes {
+    type Error = ParseError;
+
+    fn try_from(h: &Hex) -> Result<Bytes, ParseError> {
+        encoding::parse_hex(&h.0, Strictness::Strict).map(Bytes)
+    }
+}
+
+impl TryFrom<Hex> for Bytes {
+    type Error = ParseError;
+
+    fn try_from(h: Hex) -> Result<Bytes, ParseError> {
+        Bytes::try_from(&h)
+    }
+}
+
+impl TryFrom<&Base64> for Bytes {
+    type Error = ParseError;
+
+    fn try_from(b: &Base64) -> Result<Bytes, ParseError> {
+        encoding::parse_base64(&b.0, Strictness::Strict).map(Bytes)
+    }
+}
+
+impl TryFrom<Base64> for Bytes {
+    type Error = ParseError;
+
+    fn try_from(b: Base64) -> Result<Bytes, ParseError> {
+        Bytes::try_from(&b)
+    }
+}
+
+impl FromIterator<u8> for Bytes {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Bytes {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq<Vec<u8>> for Bytes {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<[u8]> for Bytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.0[..] == *other
+    }
+}
+
+/*****************
+ * FORMATTING
+ *****************/
+
+/// displays as lowercase hex
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(Format::Hex).fmt(f)
+    }
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.format {
+            Format::Hex => f.write_str(&hex::encode(self.bytes)),
+            Format::Base64 => f.write_str(&base64::encode(self.bytes)),
+            Format::EscapedAscii => self
+                .bytes
+                .iter()
+                .try_for_each(|&b| write_escaped_ascii(f, b)),
+        }
+    }
+}
+
+fn write_escaped_ascii(f: &mut fmt::Formatter, b: u8) -> fmt::Result {
+    match b {
+        b'\n' => f.write_str("\\n"),
+        b'\r' => f.write_str("\\r"),
+        b'\t' => f.write_str("\\t"),
+        b'\\' => f.write_str("\\\\"),
+        0x20..=0x7e => write!(f, "{}", b as char),
+        _ => write!(f, "\\x{:02x}", b),
+    }
+}
+
+/*****************
+ * XOR
+ *****************/
+
+/// xors two buffers byte-by-byte, truncating the result to the shorter of the two
+/// (see `xor_cypher::repeating_key_encrypt` to cycle a shorter key instead)
+impl BitXor<&Bytes> for &Bytes {
+    type Output = Bytes;
+
+    fn bitxor(self, rhs: &Bytes) -> Bytes {
+        self.iter().zip(rhs.iter()).map(|(&a, &b)| a ^ b).collect()
+    }
+}
+
+impl BitXor for Bytes {
+    type Output = Bytes;
+
+    fn bitxor(self, rhs: Bytes) -> Bytes {
+        &self ^ &rhs
+    }
+}
+
+/// xors every byte of the buffer with a single key byte
+impl BitXor<u8> for &Bytes {
+    type Output = Bytes;
+
+    fn bitxor(self, key: u8) -> Bytes {
+        self.iter().map(|&b| b ^ key).collect()
+    }
+}
+
+impl BitXor<u8> for Bytes {
+    type Output = Bytes;
+
+    fn bitxor(self, key: u8) -> Bytes {
+        &self ^ key
+    }
+}
+
+impl BitXorAssign<&Bytes> for Bytes {
+    fn bitxor_assign(&mut self, rhs: &Bytes) {
+        self.0.truncate(rhs.len());
+        self.0
+            .iter_mut()
+            .zip(rhs.iter())
+            .for_each(|(a, &b)| *a ^= b);
+    }
+}
+
+impl BitXorAssign<u8> for Bytes {
+    fn bitxor_assign(&mut self, key: u8) {
+        self.0.iter_mut().for_each(|b| *b ^= key);
+    }
+}
+
+#[cfg(test)]
+mod bytes_tests {
+    use super::*;
+
+    #[test]
+    fn converting_from_hex_and_base64() {
+        assert_eq!(
+            Bytes::try_from(Hex(String::from("68656c6c6f"))),
+            Ok(Bytes::from("hello"))
+        );
+        assert_eq!(
+            Bytes::try_from(Base64(String::from("aGVsbG8="))),
+            Ok(Bytes::from("hello"))
+        );
+        assert_eq!(
+            Bytes::try_from(Hex(String::from("68656z"))),
+            Err(ParseError::InvalidCharacter {
+                offset: 5,
+                character: 'z'
+            })
+        );
+    }
+
+    #[test]
+    fn converting_to_hex_and_base64() {
+        let bytes = Bytes::from("hello");
+        assert_eq!(Hex::from(bytes.clone()), Hex(String::from("68656c6c6f")));
+        assert_eq!(Base64::from(bytes), Base64(String::from("aGVsbG8=")));
+    }
+
+    #[test]
+    fn displaying_in_each_format() {
+        let bytes = Bytes(b"hi\n\\\x00\xff".to_vec());
+        assert_eq!(format!("{}", bytes), "68690a5c00ff");
+        assert_eq!(format!("{}", bytes.display(Format::Hex)), "68690a5c00ff");
+        assert_eq!(format!("{}", bytes.display(Format::Base64)), "aGkKXAD/");
+        assert_eq!(
+            format!("{}", bytes.display(Format::EscapedAscii)),
+            "hi\\n\\\\\\x00\\xff"
+        );
+    }
+
+    #[test]
+    fn xoring_buffers() {
+        let a = Bytes(vec![0b1100_0000, 0b0000_0000, 0b1111_1111]);
+        let b = Bytes(vec![0b0110_0000, 0b0000_0000]);
+        assert_eq!(&a ^ &b, vec![0b1010_0000, 0b0000_0000]);
+        assert_eq!(
+            &a ^ 0b1111_1111,
+            vec![0b0011_1111, 0b1111_1111, 0b0000_0000]
+        );
+
+        let mut c = a.clone();
+        c ^= &b;
+        assert_eq!(c, &a ^ &b);
+    }
+}
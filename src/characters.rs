@@ -12,105 +12,105 @@ lazy_static! {
     // source: https://reusablesec.blogspot.com/2009/05/character-frequency-analysis-info.html
     pub static ref FREQS_BY_CHAR: HashMap<u8, f64> = {
         let mut m = HashMap::<u8, f64>::new();
-        m.insert('\'' as u8, 1.22045e-06);
-        m.insert(' ' as u8, 0.166666);
-        m.insert('!' as u8, 0.000306942);
-        m.insert('\\' as u8, 1.83067e-06);
-        m.insert('#' as u8, 8.54313e-05);
-        m.insert('$' as u8, 9.70255e-05);
-        m.insert('%' as u8, 1.70863e-05);
-        m.insert('&' as u8, 1.34249e-05);
-        m.insert('(' as u8, 4.27156e-06);
-        m.insert(')' as u8, 1.15942e-05);
-        m.insert('*' as u8, 0.000241648);
-        m.insert('+' as u8, 2.31885e-05);
-        m.insert(',' as u8, 3.23418e-05);
-        m.insert('-' as u8, 0.000197712);
-        m.insert('.' as u8, 0.000316706);
-        m.insert('/' as u8, 3.11214e-05);
-        m.insert('0' as u8, 0.0274381);
-        m.insert('1' as u8, 0.0435053);
-        m.insert('2' as u8, 0.0312312);
-        m.insert('3' as u8, 0.0243339);
-        m.insert('4' as u8, 0.0194265);
-        m.insert('5' as u8, 0.0188577);
-        m.insert('6' as u8, 0.0175647);
-        m.insert('7' as u8, 0.01621);
-        m.insert('8' as u8, 0.0166225);
-        m.insert('9' as u8, 0.0179558);
-        m.insert(':' as u8, 5.49201e-06);
-        m.insert(';' as u8, 2.07476e-05);
-        m.insert('<' as u8, 4.27156e-06);
-        m.insert('=' as u8, 1.40351e-05);
-        m.insert('>' as u8, 1.83067e-06);
-        m.insert('?' as u8, 2.07476e-05);
-        m.insert('@' as u8, 0.000238597);
-        m.insert('A' as u8, 0.00130466);
-        m.insert('B' as u8, 0.000806715);
-        m.insert('C' as u8, 0.000660872);
-        m.insert('D' as u8, 0.000698096);
-        m.insert('E' as u8, 0.000970865);
-        m.insert('F' as u8, 0.000417393);
-        m.insert('G' as u8, 0.000497332);
-        m.insert('H' as u8, 0.000544319);
-        m.insert('I' as u8, 0.00070908);
-        m.insert('J' as u8, 0.000363083);
-        m.insert('K' as u8, 0.000460719);
-        m.insert('L' as u8, 0.000775594);
-        m.insert('M' as u8, 0.000782306);
-        m.insert('N' as u8, 0.000748134);
-        m.insert('O' as u8, 0.000729217);
-        m.insert('P' as u8, 0.00073715);
-        m.insert('Q' as u8, 0.000147064);
-        m.insert('R' as u8, 0.0008476);
-        m.insert('S' as u8, 0.00108132);
-        m.insert('T' as u8, 0.000801223);
-        m.insert('U' as u8, 0.000350268);
-        m.insert('V' as u8, 0.000235546);
-        m.insert('W' as u8, 0.000320367);
-        m.insert('X' as u8, 0.000142182);
-        m.insert('Y' as u8, 0.000255073);
-        m.insert('Z' as u8, 0.000170252);
-        m.insert('[' as u8, 1.0984e-05);
-        m.insert('\\' as u8, 1.15942e-05);
-        m.insert(']' as u8, 1.0984e-05);
-        m.insert('^' as u8, 1.95272e-05);
-        m.insert('_' as u8, 0.000122655);
-        m.insert('`' as u8, 1.15942e-05);
-        m.insert('a' as u8, 0.0752766);
-        m.insert('b' as u8, 0.0229145);
-        m.insert('c' as u8, 0.0257276);
-        m.insert('d' as u8, 0.0276401);
-        m.insert('e' as u8, 0.070925);
-        m.insert('f' as u8, 0.012476);
-        m.insert('g' as u8, 0.0185331);
-        m.insert('h' as u8, 0.0241319);
-        m.insert('i' as u8, 0.0469732);
-        m.insert('j' as u8, 0.00836677);
-        m.insert('k' as u8, 0.0196828);
-        m.insert('l' as u8, 0.0377728);
-        m.insert('m' as u8, 0.0299913);
-        m.insert('n' as u8, 0.0456899);
-        m.insert('o' as u8, 0.0517);
-        m.insert('p' as u8, 0.0245578);
-        m.insert('q' as u8, 0.00346119);
-        m.insert('r' as u8, 0.0496032);
-        m.insert('s' as u8, 0.0461079);
-        m.insert('t' as u8, 0.0387388);
-        m.insert('u' as u8, 0.0210191);
-        m.insert('v' as u8, 0.00833626);
-        m.insert('w' as u8, 0.0124492);
-        m.insert('x' as u8, 0.00573305);
-        m.insert('y' as u8, 0.0152483);
-        m.insert('z' as u8, 0.00632558);
-        m.insert('{' as u8, 1.22045e-06);
-        m.insert('|' as u8, 1.22045e-06);
-        m.insert('}' as u8, 0.0610223);
-        m.insert('~' as u8, 1.52556e-05);
-        m.insert('ä' as u8, 6.10223e-07);
-        m.insert('æ' as u8, 1.83067e-06);
-        m.insert('ö' as u8, 6.10223e-07);
-        m.insert('ü' as u8, 1.22045e-06);
+        m.insert(b'\'', 1.22045e-06);
+        m.insert(b' ', 0.166666);
+        m.insert(b'!', 0.000306942);
+        m.insert(b'\\', 1.83067e-06);
+        m.insert(b'#', 8.54313e-05);
+        m.insert(b'$', 9.70255e-05);
+        m.insert(b'%', 1.70863e-05);
+        m.insert(b'&', 1.34249e-05);
+        m.insert(b'(', 4.27156e-06);
+        m.insert(b')', 1.15942e-05);
+        m.insert(b'*', 0.000241648);
+        m.insert(b'+', 2.31885e-05);
+        m.insert(b',', 3.23418e-05);
+        m.insert(b'-', 0.000197712);
+        m.insert(b'.', 0.000316706);
+        m.insert(b'/', 3.11214e-05);
+        m.insert(b'0', 0.0274381);
+        m.insert(b'1', 0.0435053);
+        m.insert(b'2', 0.0312312);
+        m.insert(b'3', 0.0243339);
+        m.insert(b'4', 0.0194265);
+        m.insert(b'5', 0.0188577);
+        m.insert(b'6', 0.0175647);
+        m.insert(b'7', 0.01621);
+        m.insert(b'8', 0.0166225);
+        m.insert(b'9', 0.0179558);
+        m.insert(b':', 5.49201e-06);
+        m.insert(b';', 2.07476e-05);
+        m.insert(b'<', 4.27156e-06);
+        m.insert(b'=', 1.40351e-05);
+        m.insert(b'>', 1.83067e-06);
+        m.insert(b'?', 2.07476e-05);
+        m.insert(b'@', 0.000238597);
+        m.insert(b'A', 0.00130466);
+        m.insert(b'B', 0.000806715);
+        m.insert(b'C', 0.000660872);
+        m.insert(b'D', 0.000698096);
+        m.insert(b'E', 0.000970865);
+        m.insert(b'F', 0.000417393);
+        m.insert(b'G', 0.000497332);
+        m.insert(b'H', 0.000544319);
+        m.insert(b'I', 0.00070908);
+        m.insert(b'J', 0.000363083);
+        m.insert(b'K', 0.000460719);
+        m.insert(b'L', 0.000775594);
+        m.insert(b'M', 0.000782306);
+        m.insert(b'N', 0.000748134);
+        m.insert(b'O', 0.000729217);
+        m.insert(b'P', 0.00073715);
+        m.insert(b'Q', 0.000147064);
+        m.insert(b'R', 0.0008476);
+        m.insert(b'S', 0.00108132);
+        m.insert(b'T', 0.000801223);
+        m.insert(b'U', 0.000350268);
+        m.insert(b'V', 0.000235546);
+        m.insert(b'W', 0.000320367);
+        m.insert(b'X', 0.000142182);
+        m.insert(b'Y', 0.000255073);
+        m.insert(b'Z', 0.000170252);
+        m.insert(b'[', 1.0984e-05);
+        m.insert(b'\\', 1.15942e-05);
+        m.insert(b']', 1.0984e-05);
+        m.insert(b'^', 1.95272e-05);
+        m.insert(b'_', 0.000122655);
+        m.insert(b'`', 1.15942e-05);
+        m.insert(b'a', 0.0752766);
+        m.insert(b'b', 0.0229145);
+        m.insert(b'c', 0.0257276);
+        m.insert(b'd', 0.0276401);
+        m.insert(b'e', 0.070925);
+        m.insert(b'f', 0.012476);
+        m.insert(b'g', 0.0185331);
+        m.insert(b'h', 0.0241319);
+        m.insert(b'i', 0.0469732);
+        m.insert(b'j', 0.00836677);
+        m.insert(b'k', 0.0196828);
+        m.insert(b'l', 0.0377728);
+        m.insert(b'm', 0.0299913);
+        m.insert(b'n', 0.0456899);
+        m.insert(b'o', 0.0517);
+        m.insert(b'p', 0.0245578);
+        m.insert(b'q', 0.00346119);
+        m.insert(b'r', 0.0496032);
+        m.insert(b's', 0.0461079);
+        m.insert(b't', 0.0387388);
+        m.insert(b'u', 0.0210191);
+        m.insert(b'v', 0.00833626);
+        m.insert(b'w', 0.0124492);
+        m.insert(b'x', 0.00573305);
+        m.insert(b'y', 0.0152483);
+        m.insert(b'z', 0.00632558);
+        m.insert(b'{', 1.22045e-06);
+        m.insert(b'|', 1.22045e-06);
+        m.insert(b'}', 0.0610223);
+        m.insert(b'~', 1.52556e-05);
+        m.insert(0xE4 /* ä */, 6.10223e-07);
+        m.insert(0xE6 /* æ */, 1.83067e-06);
+        m.insert(0xF6 /* ö */, 6.10223e-07);
+        m.insert(0xFC /* ü */, 1.22045e-06);
         m
     };
 }
@@ -124,7 +124,7 @@ mod tests {
 
     #[test]
     fn test_char_lookup() {
-        assert_eq!(FREQS_BY_CHAR.get(&('a' as u8)), Some(&0.0752766))
+        assert_eq!(FREQS_BY_CHAR.get(&b'a'), Some(&0.0752766))
     }
 
     #[test]
@@ -1,6 +1,8 @@
 extern crate base64;
 extern crate hex;
 
+use std::fmt;
+
 #[derive(Debug, PartialEq)]
 pub struct Hex(pub String);
 
@@ -16,7 +18,7 @@ pub fn base64_to_bytes(b: Base64) -> Result<Vec<u8>, base64::DecodeError> {
     base64::decode(&b.0)
 }
 
-pub fn str2bytes<'a>(s: &'a String) -> &'a [u8] {
+pub fn str2bytes(s: &String) -> &[u8] {
     s.as_bytes()
 }
 
@@ -28,10 +30,155 @@ pub fn bytes2hex(bs: &[u8]) -> Hex {
     Hex(hex::encode(bs))
 }
 
-pub fn hex2bytes<'a>(h: &'a Hex) -> Vec<u8> {
+pub fn hex2bytes(h: &Hex) -> Vec<u8> {
     hex::decode(&h.0).expect("invalid hex")
 }
 
+/*******************
+ * TOLERANT PARSING
+ *******************/
+
+/// How forgiving `parse_hex` and `parse_base64` should be about formatting noise
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Strictness {
+    /// accept only the bare encoding (ie: what `hex::decode` or `base64::decode` would accept)
+    Strict,
+    /// hex: ignore whitespace, `:`/`-`/`,` separators and `0x` prefixes on each byte group
+    /// base64: ignore whitespace (ie: line-wrapped PEM-style bodies) and tolerate missing padding
+    Lenient,
+}
+
+/// Reports the first problem found in an input string. Offsets are byte offsets
+/// into the original input (not into the input with ignored characters removed).
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    InvalidCharacter {
+        offset: usize,
+        character: char,
+    },
+    /// hex input contained an odd number of digits
+    OddLength {
+        digits: usize,
+    },
+    /// base64 input contained a number of symbols that cannot encode whole bytes
+    InvalidLength {
+        symbols: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidCharacter { offset, character } => {
+                write!(f, "invalid character {:?} at offset {}", character, offset)
+            }
+            ParseError::OddLength { digits } => write!(f, "odd number of hex digits: {}", digits),
+            ParseError::InvalidLength { symbols } => {
+                write!(f, "invalid number of base64 symbols: {}", symbols)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a hex string into bytes. In `Lenient` mode, accepts input like
+/// `"0xde 0xad"`, `"de:ad:be:ef"` or bytes wrapped across several lines.
+pub fn parse_hex(s: &str, strictness: Strictness) -> Result<Vec<u8>, ParseError> {
+    let digits = significant_hex_digits(s, strictness)?;
+    if digits.len() % 2 != 0 {
+        return Err(ParseError::OddLength {
+            digits: digits.len(),
+        });
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect())
+}
+
+/// Parses a base64 string into bytes. In `Lenient` mode, accepts line-wrapped
+/// input (eg: the body of a PEM file) and input whose trailing `=` padding was dropped.
+pub fn parse_base64(s: &str, strictness: Strictness) -> Result<Vec<u8>, ParseError> {
+    let symbols = significant_base64_symbols(s, strictness)?;
+    let mut cleaned = symbols.iter().map(|&(_, c)| c).collect::<String>();
+    if strictness == Strictness::Lenient && !cleaned.ends_with('=') {
+        match cleaned.len() % 4 {
+            2 => cleaned.push_str("=="),
+            3 => cleaned.push('='),
+            _ => (),
+        }
+    }
+    base64::decode(&cleaned).map_err(|err| match err {
+        base64::DecodeError::InvalidByte(idx, _)
+        | base64::DecodeError::InvalidLastSymbol(idx, _) => {
+            let (offset, character) = symbols[idx];
+            ParseError::InvalidCharacter { offset, character }
+        }
+        base64::DecodeError::InvalidLength => ParseError::InvalidLength {
+            symbols: symbols.len(),
+        },
+    })
+}
+
+/// Collects the value of every hex digit in `s`, skipping formatting noise
+/// permitted by `strictness` and failing on the first character that is neither.
+fn significant_hex_digits(s: &str, strictness: Strictness) -> Result<Vec<u8>, ParseError> {
+    let mut digits = Vec::with_capacity(s.len());
+    let mut chars = s.char_indices().peekable();
+    let mut at_group_start = true;
+    while let Some((offset, character)) = chars.next() {
+        if strictness == Strictness::Lenient {
+            if is_hex_separator(character) {
+                at_group_start = true;
+                continue;
+            }
+            if at_group_start && character == '0' {
+                if let Some(&(_, 'x')) | Some(&(_, 'X')) = chars.peek() {
+                    chars.next();
+                    at_group_start = false;
+                    continue;
+                }
+            }
+        }
+        at_group_start = false;
+        match character.to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None => return Err(ParseError::InvalidCharacter { offset, character }),
+        }
+    }
+    Ok(digits)
+}
+
+fn is_hex_separator(c: char) -> bool {
+    c.is_whitespace() || c == ':' || c == '-' || c == ','
+}
+
+/// Collects `(offset, symbol)` pairs for every non-ignorable character in `s`,
+/// failing on the first character outside the base64 alphabet or following padding
+fn significant_base64_symbols(
+    s: &str,
+    strictness: Strictness,
+) -> Result<Vec<(usize, char)>, ParseError> {
+    let mut symbols = Vec::with_capacity(s.len());
+    let mut seen_padding = false;
+    for (offset, character) in s.char_indices() {
+        if strictness == Strictness::Lenient && character.is_whitespace() {
+            continue;
+        }
+        let valid = match character {
+            '=' => true,
+            c => !seen_padding && (c.is_ascii_alphanumeric() || c == '+' || c == '/'),
+        };
+        if !valid {
+            return Err(ParseError::InvalidCharacter { offset, character });
+        }
+        seen_padding = seen_padding || character == '=';
+        symbols.push((offset, character));
+    }
+    Ok(symbols)
+}
+
 #[cfg(test)]
 mod tests {
     // use crate::encoding::{hex_to_base64, Base64, Hex};
@@ -53,6 +200,122 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parsing_strict_hex() {
+        assert_eq!(
+            parse_hex("deadBEEF", Strictness::Strict),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            parse_hex("0xdead", Strictness::Strict),
+            Err(ParseError::InvalidCharacter {
+                offset: 1,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            parse_hex("de ad", Strictness::Strict),
+            Err(ParseError::InvalidCharacter {
+                offset: 2,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            parse_hex("dea", Strictness::Strict),
+            Err(ParseError::OddLength { digits: 3 })
+        );
+    }
+
+    #[test]
+    fn parsing_lenient_hex() {
+        let expected = Ok(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(parse_hex("0xdeadbeef", Strictness::Lenient), expected);
+        assert_eq!(
+            parse_hex("0xde 0xad 0xbe 0xef", Strictness::Lenient),
+            expected
+        );
+        assert_eq!(parse_hex("de:ad:be:ef", Strictness::Lenient), expected);
+        assert_eq!(parse_hex("  dead\n\tbeef\n", Strictness::Lenient), expected);
+        assert_eq!(parse_hex("DE-AD, 0XBE-EF", Strictness::Lenient), expected);
+    }
+
+    #[test]
+    fn reporting_position_of_invalid_hex() {
+        assert_eq!(
+            parse_hex("de:ad:bg:ef", Strictness::Lenient),
+            Err(ParseError::InvalidCharacter {
+                offset: 7,
+                character: 'g'
+            })
+        );
+        // offsets count bytes of the original input, not chars
+        assert_eq!(
+            parse_hex("dé", Strictness::Lenient),
+            Err(ParseError::InvalidCharacter {
+                offset: 1,
+                character: 'é'
+            })
+        );
+        // `0x` is only a prefix at the start of a byte group
+        assert_eq!(
+            parse_hex("de0xad", Strictness::Lenient),
+            Err(ParseError::InvalidCharacter {
+                offset: 3,
+                character: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_strict_base64() {
+        assert_eq!(
+            parse_base64("aGVsbG8=", Strictness::Strict),
+            Ok(b"hello".to_vec())
+        );
+        assert_eq!(
+            parse_base64("aGVs\nbG8=", Strictness::Strict),
+            Err(ParseError::InvalidCharacter {
+                offset: 4,
+                character: '\n'
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_lenient_base64() {
+        let wrapped = "SSdtIGtpbGxpbmcgeW91ciBicmFp\r\nbiBsaWtlIGEgcG9pc29ub3VzIG11c2hy\nb29t\n";
+        assert_eq!(
+            parse_base64(wrapped, Strictness::Lenient),
+            Ok(b"I'm killing your brain like a poisonous mushroom".to_vec())
+        );
+        assert_eq!(
+            parse_base64("aGVsbG8", Strictness::Lenient),
+            Ok(b"hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn reporting_position_of_invalid_base64() {
+        assert_eq!(
+            parse_base64("aGVs\n b*8=", Strictness::Lenient),
+            Err(ParseError::InvalidCharacter {
+                offset: 7,
+                character: '*'
+            })
+        );
+        assert_eq!(
+            parse_base64("aGVs\nbG8=ü", Strictness::Lenient),
+            Err(ParseError::InvalidCharacter {
+                offset: 9,
+                character: 'ü'
+            })
+        );
+        assert_eq!(
+            parse_base64("aGVsb", Strictness::Lenient),
+            Err(ParseError::InvalidLength { symbols: 5 })
+        );
+    }
+
     #[test]
     fn test_bytes_to_string() {
         assert_eq!(
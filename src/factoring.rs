@@ -0,0 +1,352 @@
+use crate::primality;
+use crate::rsa::{PublicKey, SecretKey};
+use bigint::BigUint;
+use num::integer::{gcd, Integer};
+use num::traits::{One, Zero};
+use num_bigint_dig as bigint;
+use std::time::{Duration, Instant};
+
+/// A factoring method: returns a nontrivial factor of n found before the deadline, if any
+pub type Method = fn(&BigUint, Instant) -> Option<BigUint>;
+
+/// The methods tried by `recover_secret_key`, cheapest (or most specialized) first
+pub const METHODS: [Method; 4] = [trial_division, fermat, pollard_p_minus_1, pollard_rho];
+
+/// trial division only tries primes below this bound
+const TRIAL_DIVISION_BOUND: usize = 1 << 20;
+
+/*******************************
+ * RSA KEY RECOVERY
+ *******************************/
+
+/// Tries to factor the modulus of `pk` with each of `METHODS` in turn, giving each
+/// `budget` to find a factor, and rebuilds the secret key from the first one found.
+///
+/// Returns `None` if no method finds a factor in time, or if the factors found aren't
+/// two primes (so that the key can't be rebuilt with `SecretKey::from_primes`).
+pub fn recover_secret_key(pk: &PublicKey, budget: Duration) -> Option<SecretKey> {
+    let p = METHODS
+        .iter()
+        .find_map(|method| method(&pk.n, Instant::now() + budget))?;
+    let q = &pk.n / &p;
+    match primality::is_probable_prime(&p) && primality::is_probable_prime(&q) {
+        true => SecretKey::from_primes(p, q, pk.e.clone()),
+        false => None,
+    }
+}
+
+/*******************************
+ * FACTORING METHODS
+ *******************************/
+
+/// Finds a (the least) prime factor of `n` below 2^20 by trial division
+pub fn trial_division(n: &BigUint, deadline: Instant) -> Option<BigUint> {
+    primal::Primes::all()
+        .take_while(|&p| p < TRIAL_DIVISION_BOUND && Instant::now() < deadline)
+        .map(BigUint::from)
+        .find(|p| p < n && (n % p).is_zero())
+}
+
+/// Finds a factor of odd `n` with Fermat's method: searches for a and b such that
+/// n = a^2 - b^2 = (a - b) * (a + b), starting from a = ceil(sqrt(n)) upwards.
+///
+/// Takes about (p - q)^2 / (8 * sqrt(n)) steps to find n = p * q, so is only
+/// practical for factors very close to sqrt(n).
+/// See: https://en.wikipedia.org/wiki/Fermat%27s_factorization_method
+pub fn fermat(n: &BigUint, deadline: Instant) -> Option<BigUint> {
+    if n.is_even() {
+        return match n > &BigUint::from(2u32) {
+            true => Some(BigUint::from(2u32)),
+            false => None,
+        };
+    }
+    let mut a = n.sqrt();
+    if &a * &a < *n {
+        a += 1u32;
+    }
+    // b2 = a^2 - n, updated as (a + 1)^2 - n = b2 + 2 * a + 1
+    let mut b2 = &a * &a - n;
+    while Instant::now() < deadline {
+        let b = b2.sqrt();
+        if &b * &b == b2 {
+            let factor = &a - &b;
+            return match factor.is_one() {
+                true => None,
+                false => Some(factor),
+            };
+        }
+        b2 += &a * 2u32 + 1u32;
+        a += 1u32;
+    }
+    None
+}
+
+/// Finds a prime factor p of `n` for which p - 1 is smooth with Pollard's p - 1 method:
+/// raises a to ever more (powers of) small primes, so that once their product M is a
+/// multiple of p - 1, a^M = 1 mod p (Fermat's little theorem), and gcd(a^M - 1, n) = p.
+///
+/// The gcd is only taken once per batch of primes, so if every prime factor of n turns
+/// up in the same batch, the batch is replayed one prime factor of M at a time. Gives up
+/// (returning `None`) if they still turn up at once.
+/// See: https://en.wikipedia.org/wiki/Pollard%27s_p_%E2%88%92_1_algorithm
+pub fn pollard_p_minus_1(n: &BigUint, deadline: Instant) -> Option<BigUint> {
+    const BATCH: usize = 100;
+
+    // a is a power of 2, so for even n it could reach 0 mod n
+    if n.is_even() || n.is_one() {
+        return match n > &BigUint::from(2u32) {
+            true => Some(BigUint::from(2u32)),
+            false => None,
+        };
+    }
+    let mut a = BigUint::from(2u32);
+    // a as of the last gcd, and the primes it has been raised to since
+    let mut checked = a.clone();
+    let mut batch = Vec::with_capacity(BATCH);
+    for p in primal::Primes::all() {
+        a = a.modpow(&BigUint::from(largest_power_below_2_32(p as u64)), n);
+        batch.push(p as u64);
+
+        let out_of_time = Instant::now() >= deadline;
+        if batch.len() == BATCH || out_of_time {
+            let d = gcd(&a - 1u32, n.clone());
+            if &d == n {
+                return replay_p_minus_1_batch(n, checked, &batch);
+            }
+            if !d.is_one() {
+                return Some(d);
+            }
+            checked = a.clone();
+            batch.clear();
+        }
+        if out_of_time {
+            return None;
+        }
+    }
+    None
+}
+
+/// Raises `a` to the primes of `batch` again, one factor at a time, taking the gcd each time
+fn replay_p_minus_1_batch(n: &BigUint, mut a: BigUint, batch: &[u64]) -> Option<BigUint> {
+    for &p in batch {
+        let p_k = largest_power_below_2_32(p);
+        let mut power = 1;
+        while power < p_k {
+            a = a.modpow(&BigUint::from(p), n);
+            power *= p;
+            let d = gcd(&a - 1u32, n.clone());
+            if !d.is_one() {
+                return match &d == n {
+                    true => None,
+                    false => Some(d),
+                };
+            }
+        }
+    }
+    None
+}
+
+/// The largest power of `p` below 2^32, to account for repeated factors of p - 1
+fn largest_power_below_2_32(p: u64) -> u64 {
+    let mut p_k = p;
+    while p_k.saturating_mul(p) < 1 << 32 {
+        p_k *= p;
+    }
+    p_k
+}
+
+/// Finds a factor of `n` with Brent's variant of Pollard's rho method: the sequence
+/// x -> x^2 + c mod n cycles mod p (for p a prime factor of n) after about sqrt(p) steps,
+/// which gcd(x - y, n) detects without knowing p. Brent's cycle detection takes fewer
+/// steps than Floyd's, and the differences are multiplied together (mod n) to take one
+/// gcd per batch of steps.
+///
+/// Takes about sqrt(p) steps, so finds small factors whatever the size of n.
+/// See: https://maths-people.anu.edu.au/~brent/pd/rpb051i.pdf
+pub fn pollard_rho(n: &BigUint, deadline: Instant) -> Option<BigUint> {
+    const BATCH: usize = 128;
+
+    // (mod 1, every gcd is n, and the sequence would be retried forever)
+    if n.is_even() || n.is_one() {
+        return fermat(n, deadline);
+    }
+    let mut c = BigUint::one();
+    while Instant::now() < deadline {
+        let f = |x: &BigUint| (x * x + &c) % n;
+        let (mut x, mut y) = (BigUint::from(2u32), BigUint::from(2u32));
+        let (mut ys, mut product) = (y.clone(), BigUint::one());
+        let mut d = BigUint::one();
+        let mut r = 1usize;
+        while d.is_one() && Instant::now() < deadline {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && d.is_one() {
+                ys = y.clone();
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(&y);
+                    product = (product * abs_diff(&x, &y)) % n;
+                }
+                d = gcd(product.clone(), n.clone());
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        // the batch overshot to a multiple of n: redo it one step at a time
+        if &d == n {
+            loop {
+                ys = f(&ys);
+                d = gcd(abs_diff(&x, &ys), n.clone());
+                if !d.is_one() {
+                    break;
+                }
+            }
+        }
+        match (d.is_one(), &d == n) {
+            (false, false) => return Some(d),
+            // the sequence cycled mod every factor at once: try another one
+            (false, true) => c += 1u32,
+            (true, _) => return None,
+        }
+    }
+    None
+}
+
+fn abs_diff(x: &BigUint, y: &BigUint) -> BigUint {
+    match x > y {
+        true => x - y,
+        false => y - x,
+    }
+}
+
+#[cfg(test)]
+mod factoring_tests {
+    use super::*;
+    use crate::rsa;
+    use bigint::RandBigInt;
+    use num::traits::ToPrimitive;
+    use rand::rngs::OsRng;
+
+    fn gen_prime(bits: usize) -> BigUint {
+        primality::gen_prime(&mut OsRng::new().expect("Failed to build RNG"), bits)
+    }
+
+    /// generates a prime p such that p - 1 = 2 * (a product of primes up to about 2^`factor_bits`)
+    fn gen_smooth_prime(bits: usize, factor_bits: usize) -> BigUint {
+        let mut rng = OsRng::new().expect("Failed to build RNG");
+        loop {
+            let mut p_minus_1 = BigUint::from(2u32);
+            while p_minus_1.bits() < bits {
+                p_minus_1 *= primality::next_prime(&rng.gen_biguint(factor_bits));
+            }
+            let p = p_minus_1 + 1u32;
+            if primality::is_probable_prime(&p) {
+                break p;
+            }
+        }
+    }
+
+    /// the bit length of the factor `p` found of `n`, if it is nontrivial
+    fn factor_bits(n: &BigUint, p: &Option<BigUint>) -> Option<usize> {
+        match p {
+            Some(p) if !p.is_one() && p != n && (n % p).is_zero() => p.bits().to_usize(),
+            _ => None,
+        }
+    }
+
+    /// a deadline no test should come near, but on a heavily loaded machine
+    fn in_a_minute() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    #[test]
+    fn finding_small_factors_by_trial_division() {
+        let n = gen_prime(256) * 65_537u32 * 65_537u32;
+        assert_eq!(trial_division(&n, in_a_minute()), Some(65_537u32.into()));
+
+        let n = gen_prime(128) * gen_prime(128);
+        assert_eq!(trial_division(&n, in_a_minute()), None);
+    }
+
+    #[test]
+    fn finding_close_factors_with_fermat() {
+        let p = gen_prime(256);
+        let q = primality::next_prime(&(&p + 1_000_000u32));
+        let n = &p * &q;
+
+        assert_eq!(fermat(&n, in_a_minute()), Some(p));
+    }
+
+    #[test]
+    fn finding_smooth_factors_with_pollard_p_minus_1() {
+        let p = gen_smooth_prime(128, 16);
+        let n = &p * gen_prime(256);
+
+        assert_eq!(pollard_p_minus_1(&n, in_a_minute()), Some(p));
+    }
+
+    #[test]
+    fn finding_factors_smooth_within_one_batch_with_pollard_p_minus_1() {
+        // every prime up to 257 is in the first batch, whose gcd is then n. The replay can
+        // only tell p from q if p - 1 and q - 1 need different largest primes, so q - 1 is
+        // built from primes up to 17 only
+        let (p, q) = (gen_smooth_prime(64, 8), gen_smooth_prime(64, 4));
+        let n = &p * &q;
+
+        let factor = pollard_p_minus_1(&n, in_a_minute());
+        assert!(factor == Some(p) || factor == Some(q));
+    }
+
+    #[test]
+    fn handling_trivial_and_even_moduli() {
+        for &n in &[0u64, 1, 2] {
+            assert_eq!(pollard_p_minus_1(&n.into(), in_a_minute()), None);
+        }
+        for &n in &[4u64, 6, 1 << 40] {
+            assert_eq!(
+                pollard_p_minus_1(&n.into(), in_a_minute()),
+                Some(2u32.into())
+            );
+        }
+        assert_eq!(pollard_rho(&BigUint::one(), in_a_minute()), None);
+        let pk = PublicKey {
+            e: rsa::DEFAULT_EXPONENT.into(),
+            n: BigUint::one(),
+        };
+        assert!(recover_secret_key(&pk, Duration::from_millis(100)).is_none());
+    }
+
+    #[test]
+    fn finding_small_factors_with_pollard_rho() {
+        let n = gen_prime(32) * gen_prime(256);
+        assert_eq!(factor_bits(&n, &pollard_rho(&n, in_a_minute())), Some(32));
+
+        // 2^64 + 1 = 274177 * 67280421310721
+        let n = (BigUint::one() << 64) + 1u32;
+        let p = pollard_rho(&n, in_a_minute());
+        assert!(p == Some(274_177u32.into()) || p == Some(67_280_421_310_721u64.into()));
+    }
+
+    #[test]
+    fn recovering_secret_key_with_close_primes() {
+        let sk = loop {
+            let p = gen_prime(256);
+            let q = primality::next_prime(&(&p + 1u32));
+            // (unless e divides p - 1 or q - 1)
+            if let Some(sk) = SecretKey::from_primes(p, q, rsa::DEFAULT_EXPONENT.into()) {
+                break sk;
+            }
+        };
+
+        let recovered = recover_secret_key(&sk.public_key(), Duration::from_secs(60)).unwrap();
+        assert_eq!(recovered.d, sk.d);
+    }
+
+    #[test]
+    fn failing_to_recover_secret_key_in_time() {
+        let (pk, _) = rsa::gen_keypair(512);
+        assert!(recover_secret_key(&pk, Duration::from_millis(100)).is_none());
+    }
+}
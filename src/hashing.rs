@@ -0,0 +1,102 @@
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+
+/// The hash functions that padding and signature schemes in this crate can be configured with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashFunction {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashFunction {
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashFunction::Sha1 => Sha1::digest(data).to_vec(),
+            HashFunction::Sha224 => Sha224::digest(data).to_vec(),
+            HashFunction::Sha256 => Sha256::digest(data).to_vec(),
+            HashFunction::Sha384 => Sha384::digest(data).to_vec(),
+            HashFunction::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// length of a digest in bytes (called `hLen` in RFC 8017)
+    pub fn output_len(self) -> usize {
+        match self {
+            HashFunction::Sha1 => 20,
+            HashFunction::Sha224 => 28,
+            HashFunction::Sha256 => 32,
+            HashFunction::Sha384 => 48,
+            HashFunction::Sha512 => 64,
+        }
+    }
+}
+
+/// Generates a mask of `mask_len` bytes from a `seed` by hashing the seed together with
+/// a 4-byte big-endian counter and concatenating the results, as specified in:
+/// https://tools.ietf.org/html/rfc8017#appendix-B.2.1
+pub fn mgf1(hash: HashFunction, seed: &[u8], mask_len: usize) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(mask_len + hash.output_len());
+    let mut counter: u32 = 0;
+    while mask.len() < mask_len {
+        let mut block = seed.to_vec();
+        block.extend_from_slice(&counter.to_be_bytes());
+        mask.extend(hash.digest(&block));
+        counter += 1;
+    }
+    mask.truncate(mask_len);
+    mask
+}
+
+#[cfg(test)]
+mod hashing_tests {
+    use super::*;
+    use crate::encoding::{self, Hex};
+
+    #[test]
+    fn hashing_with_each_function() {
+        let digests = [
+            HashFunction::Sha1,
+            HashFunction::Sha224,
+            HashFunction::Sha256,
+            HashFunction::Sha384,
+            HashFunction::Sha512,
+        ]
+        .iter()
+        .map(|&hash| (hash, hash.digest(b"abc")))
+        .collect::<Vec<_>>();
+
+        digests
+            .iter()
+            .for_each(|(hash, digest)| assert_eq!(digest.len(), hash.output_len()));
+        assert_eq!(
+            encoding::bytes2hex(&digests[0].1),
+            Hex(String::from("a9993e364706816aba3e25717850c26c9cd0d89d"))
+        );
+        assert_eq!(
+            encoding::bytes2hex(&digests[2].1),
+            Hex(String::from(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            ))
+        );
+    }
+
+    #[test]
+    fn generating_masks_with_mgf1() {
+        // examples from: https://en.wikipedia.org/wiki/Mask_generation_function
+        assert_eq!(
+            encoding::bytes2hex(&mgf1(HashFunction::Sha1, b"foo", 3)),
+            Hex(String::from("1ac907"))
+        );
+        assert_eq!(
+            encoding::bytes2hex(&mgf1(HashFunction::Sha1, b"foo", 5)),
+            Hex(String::from("1ac9075cd4"))
+        );
+        assert_eq!(
+            encoding::bytes2hex(&mgf1(HashFunction::Sha256, b"bar", 50)),
+            Hex(String::from("382576a7841021cc28fc4c0948753fb8312090cea942ea4c4e735d10dc724b155f9f6069f289d61daca0cb814502ef04eae1"))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::xor_attack_repeating;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// number of bytes rendered per line by `hexdump`
+pub const HEXDUMP_WIDTH: usize = 16;
+/// number of bytes rendered per line (for each input) by `side_by_side`
+pub const SIDE_BY_SIDE_WIDTH: usize = 8;
+
+/// renders bytes in the style of `hexdump -C`, ie:
+///
+/// ```text
+/// 00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a       |Hello, world!.|
+/// ```
+pub fn hexdump<T: AsRef<[u8]>>(bytes: T) -> String {
+    bytes
+        .as_ref()
+        .chunks(HEXDUMP_WIDTH)
+        .enumerate()
+        .map(|(idx, row)| {
+            format!(
+                "{:08x}  {} |{}|\n",
+                idx * HEXDUMP_WIDTH,
+                hex_column(row, HEXDUMP_WIDTH),
+                ascii_column(row)
+            )
+        })
+        .collect()
+}
+
+/// renders two byte arrays (typically a cyphertext and a candidate cleartext)
+/// in adjacent hexdump columns so that corresponding bytes share a line
+pub fn side_by_side<A: AsRef<[u8]>, B: AsRef<[u8]>>(left: A, right: B) -> String {
+    let (left, right) = (left.as_ref(), right.as_ref());
+    let num_rows = left.len().max(right.len()).div_ceil(SIDE_BY_SIDE_WIDTH);
+    (0..num_rows)
+        .map(|idx| {
+            let (l, r) = (nth_row(left, idx), nth_row(right, idx));
+            format!(
+                "{:08x}  {} |{:<w$}|  {} |{:<w$}|\n",
+                idx * SIDE_BY_SIDE_WIDTH,
+                hex_column(l, SIDE_BY_SIDE_WIDTH),
+                ascii_column(l),
+                hex_column(r, SIDE_BY_SIDE_WIDTH),
+                ascii_column(r),
+                w = SIDE_BY_SIDE_WIDTH
+            )
+        })
+        .collect()
+}
+
+/// renders bytes as one `keysize`-length block per line, under a header numbering
+/// the key position of each column (so bytes encrypted by the same key byte line up).
+/// Blocks that occur more than once are flagged: the first occurrence with `*`,
+/// later ones with the index of the block they repeat (a telltale of ECB mode).
+/// A `keysize` of 0 has no columns to render, so yields an empty view.
+pub fn block_view<T: AsRef<[u8]>>(bytes: T, keysize: usize) -> String {
+    if keysize == 0 {
+        return String::new();
+    }
+    let bytes = bytes.as_ref();
+    let full_blocks = match bytes.len() / keysize {
+        0 => vec![],
+        n => xor_attack_repeating::partition(bytes, n, keysize),
+    };
+    let remainder = &bytes[(full_blocks.len() * keysize)..];
+    let repeats = find_repeated_blocks(&full_blocks);
+
+    let mut out = format!("{:14}", "");
+    (0..keysize).for_each(|col| write!(out, " {:>2}", col).unwrap());
+    out.push('\n');
+    for (idx, block) in full_blocks.iter().enumerate() {
+        let marker = match repeats.get(&idx) {
+            Some(&first) if first == idx => String::from("  *"),
+            Some(&first) => format!("  = block {}", first),
+            None => String::new(),
+        };
+        writeln!(out, "{}{}", block_line(idx, keysize, block), marker).unwrap();
+    }
+    if !remainder.is_empty() {
+        writeln!(out, "{}", block_line(full_blocks.len(), keysize, remainder)).unwrap();
+    }
+    out
+}
+
+/// maps the index of every block that occurs more than once to the index of its first occurrence
+fn find_repeated_blocks(blocks: &[&[u8]]) -> HashMap<usize, usize> {
+    let mut first_seen = HashMap::<&[u8], usize>::new();
+    let mut counts = HashMap::<&[u8], usize>::new();
+    for (idx, &block) in blocks.iter().enumerate() {
+        first_seen.entry(block).or_insert(idx);
+        *counts.entry(block).or_insert(0) += 1;
+    }
+    blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| counts[*block] > 1)
+        .map(|(idx, block)| (idx, first_seen[block]))
+        .collect()
+}
+
+fn block_line(idx: usize, keysize: usize, block: &[u8]) -> String {
+    let hex = block
+        .iter()
+        .map(|b| format!(" {:02x}", b))
+        .collect::<String>();
+    format!("{:08x} {:>5}{}", idx * keysize, idx, hex)
+}
+
+/// hex bytes separated by spaces, with an extra space at the midpoint,
+/// padded so that a short final row still lines up with full ones
+fn hex_column(row: &[u8], width: usize) -> String {
+    let mut out = String::with_capacity(width * 3 + 1);
+    for idx in 0..width {
+        if idx == width / 2 {
+            out.push(' ');
+        }
+        match row.get(idx) {
+            Some(b) => write!(out, "{:02x} ", b).unwrap(),
+            None => out.push_str("   "),
+        }
+    }
+    out.pop();
+    out
+}
+
+/// printable ASCII as-is, everything else as `.`
+fn ascii_column(row: &[u8]) -> String {
+    row.iter()
+        .map(|&b| match b {
+            0x20..=0x7e => b as char,
+            _ => '.',
+        })
+        .collect()
+}
+
+fn nth_row(bytes: &[u8], idx: usize) -> &[u8] {
+    let start = (idx * SIDE_BY_SIDE_WIDTH).min(bytes.len());
+    let end = (start + SIDE_BY_SIDE_WIDTH).min(bytes.len());
+    &bytes[start..end]
+}
+
+#[cfg(test)]
+mod hexdump_tests {
+    use super::*;
+
+    #[test]
+    fn dumping_bytes_as_hex_and_ascii() {
+        assert_eq!(
+            hexdump(b"Hello, world!\nHow are you today?"),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 48 6f |Hello, world!.Ho|\n\
+             00000010  77 20 61 72 65 20 79 6f  75 20 74 6f 64 61 79 3f |w are you today?|\n"
+        );
+        assert_eq!(
+            hexdump([0x00, 0x41, 0xff]),
+            "00000000  00 41 ff                                         |.A.|\n"
+        );
+        assert_eq!(hexdump([]), "");
+    }
+
+    #[test]
+    fn dumping_two_byte_arrays_side_by_side() {
+        assert_eq!(
+            side_by_side(b"\x01\x02\x03\x04\x05\x06\x07\x08\x09", b"cleartext"),
+            "00000000  01 02 03 04  05 06 07 08 |........|  63 6c 65 61  72 74 65 78 |cleartex|\n\
+             00000008  09                       |.       |  74                       |t       |\n"
+        );
+    }
+
+    #[test]
+    fn viewing_blocks_aligned_by_keysize() {
+        let bytes = [
+            0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x03, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x05, 0x06,
+        ];
+        assert_eq!(
+            block_view(&bytes[..], 4),
+            "                0  1  2  3\n\
+             00000000     0 de ad be ef  *\n\
+             00000004     1 01 02 03 04\n\
+             00000008     2 de ad be ef  = block 0\n\
+             0000000c     3 05 06\n"
+        );
+    }
+
+    #[test]
+    fn viewing_blocks_of_input_shorter_than_keysize() {
+        assert_eq!(
+            block_view([0xaa], 2),
+            "                0  1\n00000000     0 aa\n"
+        );
+        assert_eq!(block_view([], 2), "                0  1\n");
+    }
+
+    #[test]
+    fn viewing_blocks_of_no_keysize() {
+        assert_eq!(block_view([0xaa, 0xbb], 0), "");
+    }
+}
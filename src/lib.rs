@@ -1,9 +1,26 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod bytes;
 pub mod characters;
 pub mod encoding;
+pub mod factoring;
+pub mod hashing;
+pub mod hexdump;
+pub mod montgomery;
+pub mod number_theory;
+pub mod primality;
 pub mod rsa;
+pub mod rsa_attack_batch_gcd;
+pub mod rsa_attack_bleichenbacher;
+pub mod rsa_attack_broadcast;
+pub mod rsa_attack_common_modulus;
+pub mod rsa_attack_fault;
+pub mod rsa_attack_franklin_reiter;
+pub mod rsa_attack_parity;
+pub mod rsa_attack_signature_forgery;
+pub mod rsa_attack_unpadded;
+pub mod rsa_attack_wiener;
 pub mod scoring;
 pub mod xor_attack;
 pub mod xor_attack_repeating;
@@ -12,6 +29,7 @@ pub mod xor_cypher;
 #[cfg(test)]
 mod test_set_1 {
     use super::*;
+    use bytes::Bytes;
     use encoding::{Base64, Hex};
     use std::path::Path;
 
@@ -86,7 +104,7 @@ mod test_set_1 {
         )));
         assert_eq!(
             xor_attack::brute_force_decrypt(&cyphertext),
-            String::from("Cooking MC's like a pound of bacon")
+            Bytes::from("Cooking MC's like a pound of bacon")
         )
     }
 
@@ -98,10 +116,10 @@ mod test_set_1 {
          **/
 
         let path = Path::new("data/detect_single_byte_xor.txt");
-        let cyphertext = xor_attack::detect_xor_encryption_from_file(&path);
+        let cyphertext = xor_attack::detect_xor_encryption_from_file(path);
         assert_eq!(
             xor_attack::brute_force_decrypt(&cyphertext),
-            String::from("Now that the party is jumping\n"),
+            Bytes::from("Now that the party is jumping\n"),
         )
     }
 
@@ -180,6 +198,166 @@ mod test_set_1 {
             .replace("\n", "");
         let cyphertext = encoding::base64_to_bytes(Base64(cyphertext_b64)).unwrap();
         let cleartext = xor_attack_repeating::brute_force_decrypt(&cyphertext);
-        assert_eq!(cleartext, String::from("I\'m back and I\'m ringin\' the bell \nA rockin\' on the mike while the fly girls yell \nIn ecstasy in the back of me \nWell that\'s my DJ Deshay cuttin\' all them Z\'s \nHittin\' hard and the girlies goin\' crazy \nVanilla\'s on the mike, man I\'m not lazy. \n\nI\'m lettin\' my drug kick in \nIt controls my mouth and I begin \nTo just let it flow, let my concepts go \nMy posse\'s to the side yellin\', Go Vanilla Go! \n\nSmooth \'cause that\'s the way I will be \nAnd if you don\'t give a damn, then \nWhy you starin\' at me \nSo get off \'cause I control the stage \nThere\'s no dissin\' allowed \nI\'m in my own phase \nThe girlies sa y they love me and that is ok \nAnd I can dance better than any kid n\' play \n\nStage 2 -- Yea the one ya\' wanna listen to \nIt\'s off my head so let the beat play through \nSo I can funk it up and make it sound good \n1-2-3 Yo -- Knock on some wood \nFor good luck, I like my rhymes atrocious \nSupercalafragilisticexpialidocious \nI\'m an effect and that you can bet \nI can take a fly girl and make her wet. \n\nI\'m like Samson -- Samson to Delilah \nThere\'s no denyin\', You can try to hang \nBut you\'ll keep tryin\' to get my style \nOver and over, practice makes perfect \nBut not if you\'re a loafer. \n\nYou\'ll get nowhere, no place, no time, no girls \nSoon -- Oh my God, homebody, you probably eat \nSpaghetti with a spoon! Come on and say it! \n\nVIP. Vanilla Ice yep, yep, I\'m comin\' hard like a rhino \nIntoxicating so you stagger like a wino \nSo punks stop trying and girl stop cryin\' \nVanilla Ice is sellin\' and you people are buyin\' \n\'Cause why the freaks are jockin\' like Crazy Glue \nMovin\' and groovin\' trying to sing along \nAll through the ghetto groovin\' this here song \nNow you\'re amazed by the VIP posse. \n\nSteppin\' so hard like a German Nazi \nStartled by the bases hittin\' ground \nThere\'s no trippin\' on mine, I\'m just gettin\' down \nSparkamatic, I\'m hangin\' tight like a fanatic \nYou trapped me once and I thought that \nYou might have it \nSo step down and lend me your ear \n\'89 in my time! You, \'90 is my year. \n\nYou\'re weakenin\' fast, YO! and I can tell it \nYour body\'s gettin\' hot, so, so I can smell it \nSo don\'t be mad and don\'t be sad \n\'Cause the lyrics belong to ICE, You can call me Dad \nYou\'re pitchin\' a fit, so step back and endure \nLet the witch doctor, Ice, do the dance to cure \nSo come up close and don\'t be square \nYou wanna battle me -- Anytime, anywhere \n\nYou thought that I was weak, Boy, you\'re dead wrong \nSo come on, everybody and sing this song \n\nSay -- Play that funky music Say, go white boy, go white boy go \nplay that funky music Go white boy, go white boy, go \nLay down and boogie and play that funky music till you die. \n\nPlay that funky music Come on, Come on, let me hear \nPlay that funky music white boy you say it, say it \nPlay that funky music A little louder now \nPlay that funky music, white boy Come on, Come on, Come on \nPlay that funky mu"));
+        assert_eq!(cleartext, Bytes::from("I\'m back and I\'m ringin\' the bell \nA rockin\' on the mike while the fly girls yell \nIn ecstasy in the back of me \nWell that\'s my DJ Deshay cuttin\' all them Z\'s \nHittin\' hard and the girlies goin\' crazy \nVanilla\'s on the mike, man I\'m not lazy. \n\nI\'m lettin\' my drug kick in \nIt controls my mouth and I begin \nTo just let it flow, let my concepts go \nMy posse\'s to the side yellin\', Go Vanilla Go! \n\nSmooth \'cause that\'s the way I will be \nAnd if you don\'t give a damn, then \nWhy you starin\' at me \nSo get off \'cause I control the stage \nThere\'s no dissin\' allowed \nI\'m in my own phase \nThe girlies sa y they love me and that is ok \nAnd I can dance better than any kid n\' play \n\nStage 2 -- Yea the one ya\' wanna listen to \nIt\'s off my head so let the beat play through \nSo I can funk it up and make it sound good \n1-2-3 Yo -- Knock on some wood \nFor good luck, I like my rhymes atrocious \nSupercalafragilisticexpialidocious \nI\'m an effect and that you can bet \nI can take a fly girl and make her wet. \n\nI\'m like Samson -- Samson to Delilah \nThere\'s no denyin\', You can try to hang \nBut you\'ll keep tryin\' to get my style \nOver and over, practice makes perfect \nBut not if you\'re a loafer. \n\nYou\'ll get nowhere, no place, no time, no girls \nSoon -- Oh my God, homebody, you probably eat \nSpaghetti with a spoon! Come on and say it! \n\nVIP. Vanilla Ice yep, yep, I\'m comin\' hard like a rhino \nIntoxicating so you stagger like a wino \nSo punks stop trying and girl stop cryin\' \nVanilla Ice is sellin\' and you people are buyin\' \n\'Cause why the freaks are jockin\' like Crazy Glue \nMovin\' and groovin\' trying to sing along \nAll through the ghetto groovin\' this here song \nNow you\'re amazed by the VIP posse. \n\nSteppin\' so hard like a German Nazi \nStartled by the bases hittin\' ground \nThere\'s no trippin\' on mine, I\'m just gettin\' down \nSparkamatic, I\'m hangin\' tight like a fanatic \nYou trapped me once and I thought that \nYou might have it \nSo step down and lend me your ear \n\'89 in my time! You, \'90 is my year. \n\nYou\'re weakenin\' fast, YO! and I can tell it \nYour body\'s gettin\' hot, so, so I can smell it \nSo don\'t be mad and don\'t be sad \n\'Cause the lyrics belong to ICE, You can call me Dad \nYou\'re pitchin\' a fit, so step back and endure \nLet the witch doctor, Ice, do the dance to cure \nSo come up close and don\'t be square \nYou wanna battle me -- Anytime, anywhere \n\nYou thought that I was weak, Boy, you\'re dead wrong \nSo come on, everybody and sing this song \n\nSay -- Play that funky music Say, go white boy, go white boy go \nplay that funky music Go white boy, go white boy, go \nLay down and boogie and play that funky music till you die. \n\nPlay that funky music Come on, Come on, let me hear \nPlay that funky music white boy you say it, say it \nPlay that funky music A little louder now \nPlay that funky music, white boy Come on, Come on, Come on \nPlay that funky mu"));
+    }
+}
+
+#[cfg(test)]
+mod test_set_5 {
+    use super::*;
+    use bytes::Bytes;
+    use encoding::Base64;
+    use num_bigint_dig::BigUint;
+
+    #[test]
+    fn challenge_40() {
+        /*****
+         * Implement an E=3 RSA Broadcast attack
+         *
+         * Assume you're a Javascript programmer. That is, you're using a naive handrolled RSA to encrypt without padding.
+         *
+         * Assume you can be coerced into encrypting the same plaintext three times, under three different public keys. You can; it's happened.
+         *
+         * Then an attacker can trivially decrypt your message, by:
+         *
+         * 1. Capturing any 3 of the ciphertexts and their corresponding pubkeys
+         *
+         * 2. Using the CRT to solve for the number represented by the three ciphertexts (which are residues mod their respective pubkeys)
+         *
+         * 3. Taking the cube root of the resulting number
+         *****/
+
+        let m = BigUint::from_bytes_be(b"Assume you're a Javascript programmer");
+        let cyphertexts = (0..3)
+            .map(|_| {
+                let (pk, _) = rsa::gen_keypair_with_exponent(512, &BigUint::from(3u32)).unwrap();
+                (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rsa_attack_broadcast::recover_broadcast_message(&cyphertexts),
+            Ok(m)
+        );
+    }
+
+    #[test]
+    fn challenge_41() {
+        /*****
+         * Implement unpadded message recovery oracle
+         *
+         * Nate Lawson says we should stop calling it "RSA padding" and start calling it "RSA armoring". Here's why.
+         *
+         * Imagine a web application, again with the Javascript encryption, taking RSA-encrypted messages which (again: Javascript) aren't padded before encryption at all.
+         *
+         * You can submit an arbitrary RSA blob and the server will return plaintext. But you can't submit the same message twice: let's say the server keeps hashes of previous messages for some liveness interval, and that the message has an embedded timestamp.
+         *
+         * You'd like to capture other people's messages and use the server to decrypt them. But when you try, the server takes the hash of the ciphertext and uses it to reject the request.
+         *****/
+        use rsa_attack_unpadded::{DecryptionOracle, DecryptionServer, ServerError};
+
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = DecryptionServer::new(sk);
+        let m = BigUint::from_bytes_be(b"{time: 1356304276, social: '555-55-5555'}");
+        let c = rsa::encrypt_unpadded(&m, &pk).unwrap();
+        assert_eq!(server.decrypt(&c), Ok(m.clone()));
+        assert_eq!(server.decrypt(&c), Err(ServerError::AlreadySeen));
+
+        assert_eq!(
+            rsa_attack_unpadded::recover_message(&mut server, &pk, &c),
+            Ok(m)
+        );
+    }
+
+    #[test]
+    fn challenge_42() {
+        /*****
+         * Bleichenbacher's e=3 RSA Attack
+         *
+         * RSA with an encrypting exponent of 3 is popular, because it makes the RSA math faster.
+         *
+         * With e=3 RSA, encryption is just cubing a number mod the public encryption modulus. [...] Some verifiers [...] look for the 00h 01h FFh [...] FFh 00h ASN.1 HASH pattern, without checking that the hash is right-justified.
+         *
+         * [...] you can build a fake block: 00h 01h FFh 00h ASN.1 GOOP HASH, then garbage, and take its cube root. [...] Forge a 1024-bit RSA signature for the string "hi mom".
+         *****/
+        use hashing::HashFunction;
+        use rsa_attack_signature_forgery as forgery;
+
+        let (pk, _) = rsa::gen_keypair_with_exponent(1024, &BigUint::from(3u32)).unwrap();
+        let s = forgery::forge_signature(b"hi mom", &pk, HashFunction::Sha1).unwrap();
+
+        assert!(forgery::verify_pkcs1v15_sloppily(b"hi mom", &s, &pk, HashFunction::Sha1).is_ok());
+        assert!(rsa::verify_pkcs1v15(b"hi mom", &s, &pk, HashFunction::Sha1).is_err());
+    }
+
+    #[test]
+    fn challenge_46() {
+        /*****
+         * RSA parity oracle
+         *
+         * Generate a 1024 bit RSA key pair.
+         *
+         * Write an oracle function that uses the private key to answer the question "is the plaintext of this message even or odd" (is the last bit of the message 0 or 1). Imagine for instance a server that accepted RSA-encrypted messages and checked the parity of their decryption to validate them, and spat out an error if they were of the wrong parity.
+         *
+         * [...] Decode the following base64 string, encrypt it with the public key, and use the oracle to recover it:
+         *
+         * VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==
+         *
+         * [...] Be sure to write this so that you can print the upper bound of the plaintext as a string, and watch the message decrypt "hollywood style".
+         *****/
+        use rsa_attack_parity::ParityServer;
+
+        let (pk, sk) = rsa::gen_keypair(1024);
+        let m = encoding::base64_to_bytes(Base64(String::from(
+            "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==",
+        )))
+        .unwrap();
+        let c = rsa::encrypt_unpadded(&BigUint::from_bytes_be(&m), &pk).unwrap();
+
+        let mut bounds = vec![];
+        let m_ = rsa_attack_parity::recover_message_with_progress(
+            &mut ParityServer::new(sk),
+            &pk,
+            &c,
+            |hi| bounds.push(hi.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Bytes(m_.to_bytes_be()),
+            Bytes::from("That's why I found you don't play around with the Funky Cold Medina")
+        );
+        // printed one after the other, the upper bounds decrypt "hollywood style"
+        assert_eq!(bounds.len(), 1024);
+        assert!(bounds.windows(2).all(|pair| pair[1] <= pair[0]));
+        let hollywood_style = Bytes(bounds[1023].to_bytes_be()).to_string_lossy();
+        assert!(
+            hollywood_style.starts_with("That's why I found you don't play around with the Funky")
+        );
+    }
+
+    #[test]
+    fn challenge_47() {
+        /*****
+         * Bleichenbacher's PKCS 1.5 Padding Oracle (Simple Case)
+         *
+         * Build an oracle function, just like you did in the last exercise, but have it check for plaintext[0] == 0 and plaintext[1] == 2.
+         *
+         * Generate a 256 bit keypair (that is, p and q will each be 128 bit primes), [n, e, d].
+         *
+         * PKCS1.5-pad a short message, like "kick it, CC", and call it "m". Encrypt to to get "c".
+         *
+         * Decrypt "c" using your padding oracle.
+         *****/
+        use rsa_attack_bleichenbacher::PaddingServer;
+
+        let (pk, sk) = rsa::gen_keypair(256);
+        let c = rsa::encrypt_pkcs1v15(b"kick it, CC", &pk).unwrap();
+        let em = rsa_attack_bleichenbacher::recover_message(&mut PaddingServer::new(sk), &pk, &c)
+            .unwrap()
+            .to_bytes_be();
+
+        assert_eq!(em[0], 0x02); // the leading 0x00 is dropped by `to_bytes_be`
+        assert!(em.ends_with(b"\x00kick it, CC"));
     }
 }
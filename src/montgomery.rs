@@ -0,0 +1,266 @@
+use num::integer::Integer;
+use num::traits::One;
+use num_bigint_dig::BigUint;
+
+/// A modulus prepared for Montgomery multiplication, which replaces the division of each
+/// modular reduction with multiplications and shifts, as described in:
+/// https://en.wikipedia.org/wiki/Montgomery_modular_multiplication
+///
+/// Numbers are kept in Montgomery form, x * R mod n with R = 2^(64 * k) for k the number of
+/// 64-bit limbs of n, as fixed-length limb vectors, so that multiplying them takes no
+/// division and no normalization. Preparing the modulus costs about one ordinary exponentiation
+/// step, so is worth it whenever many multiplications follow, as in an exponentiation.
+///
+/// `BigUint::modpow` already uses Montgomery multiplication for odd moduli, so this is no
+/// faster for public exponents. What it adds is `ladder_modpow`, for private key operations:
+/// fixed-length limb vectors take the same steps to multiply whatever their values, where
+/// `BigUint`'s (normalized, variable-length) arithmetic doesn't.
+pub struct Montgomery {
+    n: Vec<u64>,
+    /// -n^-1 mod 2^64
+    n_inv: u64,
+    /// R^2 mod n, to convert numbers into Montgomery form
+    r_squared: Vec<u64>,
+}
+
+impl Montgomery {
+    /// Prepares odd `n` > 1 (so that R is invertible mod n), or returns `None`
+    pub fn new(n: &BigUint) -> Option<Montgomery> {
+        if n.is_even() || n.is_one() {
+            return None;
+        }
+        let limbs = to_limbs(n, n.bits().div_ceil(64));
+        // Newton's iteration doubles the number of correct low bits of n^-1 each step:
+        // n * n = 1 mod 8, so 3 bits to start with, then 6, 12, 24, 48 and 96
+        let mut inv = limbs[0];
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(limbs[0].wrapping_mul(inv)));
+        }
+        let r_squared = (BigUint::one() << (128 * limbs.len())) % n;
+        Some(Montgomery {
+            r_squared: to_limbs(&r_squared, limbs.len()),
+            n_inv: inv.wrapping_neg(),
+            n: limbs,
+        })
+    }
+
+    /// `a` * `b` mod n
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let product = self.mont_mul(&self.montgomery_form(a), &self.montgomery_form(b));
+        self.standard_form(&product)
+    }
+
+    /// `base`^`exp` mod n with a Montgomery ladder:
+    /// https://en.wikipedia.org/wiki/Exponentiation_by_squaring#Montgomery's_ladder_technique
+    ///
+    /// Every bit of the exponent (up to the limb length of n, which must not be shorter than
+    /// `exp`, so leading zeros and the exponent's length stay hidden) costs exactly one
+    /// multiplication and one squaring, whatever its value, and the ladder's swaps are masked
+    /// rather than branched on. Montgomery multiplication itself takes the same steps whatever
+    /// its operands (its final subtraction included).
+    pub fn ladder_modpow(&self, base: &BigUint, exp: &BigUint) -> BigUint {
+        let k = self.n.len();
+        debug_assert!(exp.bits() <= 64 * k);
+        let exp = to_limbs(exp, k);
+        let mut r0 = self.montgomery_form(&BigUint::one());
+        let mut r1 = self.montgomery_form(base);
+        for i in (0..64 * k).rev() {
+            let bit = (exp[i / 64] >> (i % 64)) & 1;
+            // with r0 and r1 in canonical order: (r0, r1) = (r0^2, r0 * r1)
+            conditional_swap(&mut r0, &mut r1, bit);
+            r1 = self.mont_mul(&r0, &r1);
+            r0 = self.mont_mul(&r0, &r0);
+            conditional_swap(&mut r0, &mut r1, bit);
+        }
+        self.standard_form(&r0)
+    }
+
+    /// x * R mod n, for any x. Splitting x into k-limb chunks c_i, x * R = sum(c_i * R^(i + 1)),
+    /// which Horner's rule accumulates from the top chunk down with Montgomery multiplications
+    /// by R^2 (each one multiplying by R), rather than by reducing x with a division.
+    fn montgomery_form(&self, x: &BigUint) -> Vec<u64> {
+        let k = self.n.len();
+        let chunks = x.bits().div_ceil(64 * k).max(1);
+        let mut acc = vec![0u64; k];
+        for chunk in to_limbs(x, chunks * k).chunks(k).rev() {
+            let shifted = self.mont_mul(&acc, &self.r_squared);
+            acc = self.add(&shifted, &self.mont_mul(chunk, &self.r_squared));
+        }
+        acc
+    }
+
+    fn standard_form(&self, x: &[u64]) -> BigUint {
+        let mut one = vec![0; self.n.len()];
+        one[0] = 1;
+        from_limbs(&self.mont_mul(x, &one))
+    }
+
+    /// a + b mod n, for a and b < n
+    fn add(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let k = self.n.len();
+        let mut t = vec![0u64; k + 1];
+        let mut carry = 0u64;
+        for j in 0..k {
+            let sum = a[j] as u128 + b[j] as u128 + carry as u128;
+            t[j] = sum as u64;
+            carry = (sum >> 64) as u64;
+        }
+        t[k] = carry;
+        self.reduce_once(&t)
+    }
+
+    /// a * b * R^-1 mod n, for a < R and b < n, by (finely integrated operand scanning):
+    /// https://www.microsoft.com/en-us/research/wp-content/uploads/1996/01/j37acmon.pdf
+    fn mont_mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let (k, n) = (self.n.len(), &self.n);
+        let mut t = vec![0u64; k + 1];
+        for &a_i in a {
+            // t = (t + a_i * b + m * n) / 2^64, with m chosen so that the division is exact
+            let sum = (a_i as u128).wrapping_mul(b[0] as u128) + t[0] as u128;
+            let m = (sum as u64).wrapping_mul(self.n_inv);
+            let mut carry_ab = (sum >> 64) as u64;
+            let sum = (m as u128).wrapping_mul(n[0] as u128) + (sum as u64) as u128;
+            let mut carry_mn = (sum >> 64) as u64;
+            let mut j = 1;
+            while j < k {
+                let sum =
+                    (a_i as u128).wrapping_mul(b[j] as u128) + t[j] as u128 + carry_ab as u128;
+                carry_ab = (sum >> 64) as u64;
+                let sum = (m as u128).wrapping_mul(n[j] as u128)
+                    + (sum as u64) as u128
+                    + carry_mn as u128;
+                carry_mn = (sum >> 64) as u64;
+                t[j - 1] = sum as u64;
+                j += 1;
+            }
+            let sum = t[k] as u128 + carry_ab as u128 + carry_mn as u128;
+            t[k - 1] = sum as u64;
+            t[k] = (sum >> 64) as u64;
+        }
+        // (a * b + m * n) / R < (R * n + R * n) / R = 2n
+        self.reduce_once(&t)
+    }
+
+    /// t mod n, for t < 2n of k + 1 limbs: subtracts n, and keeps the difference unless that
+    /// borrowed (and t < n), selecting with a mask rather than a branch
+    fn reduce_once(&self, t: &[u64]) -> Vec<u64> {
+        let (k, n) = (self.n.len(), &self.n);
+        let mut diff = vec![0u64; k];
+        let mut borrow = 0u64;
+        for j in 0..k {
+            let (d, b1) = t[j].overflowing_sub(n[j]);
+            let (d, b2) = d.overflowing_sub(borrow);
+            diff[j] = d;
+            borrow = (b1 | b2) as u64;
+        }
+        let (_, borrowed) = t[k].overflowing_sub(borrow);
+        let keep_t = (borrowed as u64).wrapping_neg();
+        for j in 0..k {
+            diff[j] = (t[j] & keep_t) | (diff[j] & !keep_t);
+        }
+        diff
+    }
+}
+
+/// Swaps `a` and `b` if `bit` is 1 (and leaves them if it is 0), with the same operations
+/// either way: https://tools.ietf.org/html/rfc7748#section-5 (cswap)
+fn conditional_swap(a: &mut [u64], b: &mut [u64], bit: u64) {
+    let mask = 0u64.wrapping_sub(bit);
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let d = (*x ^ *y) & mask;
+        *x ^= d;
+        *y ^= d;
+    }
+}
+
+fn to_limbs(x: &BigUint, len: usize) -> Vec<u64> {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(8 * len, 0);
+    bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut limb = [0u8; 8];
+            limb.copy_from_slice(chunk);
+            u64::from_le_bytes(limb)
+        })
+        .collect()
+}
+
+fn from_limbs(limbs: &[u64]) -> BigUint {
+    let bytes = limbs
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes().to_vec())
+        .collect::<Vec<_>>();
+    BigUint::from_bytes_le(&bytes)
+}
+
+#[cfg(test)]
+mod montgomery_tests {
+    use super::*;
+    use num_bigint_dig::RandBigInt;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn rejecting_even_moduli() {
+        assert!(Montgomery::new(&BigUint::from(1u32)).is_none());
+        assert!(Montgomery::new(&BigUint::from(1u32 << 20)).is_none());
+        assert!(Montgomery::new(&BigUint::from(3u32)).is_some());
+    }
+
+    #[test]
+    fn multiplying_matches_biguint() {
+        let mut rng = ChaChaRng::seed_from_u64(49);
+        for &bits in &[2, 63, 64, 65, 512, 1000] {
+            let n = rng.gen_biguint(bits) | BigUint::one() | (BigUint::one() << (bits - 1));
+            let mont = Montgomery::new(&n).unwrap();
+            for _ in 0..20 {
+                let (a, b) = (rng.gen_biguint(bits + 10), rng.gen_biguint(bits));
+                assert_eq!(mont.mul(&a, &b), (a * b) % &n);
+            }
+            // operands at the edge of the range, where the final subtraction matters most
+            let n_minus_1 = &n - 1u32;
+            assert_eq!(
+                mont.mul(&n_minus_1, &n_minus_1),
+                (&n_minus_1 * &n_minus_1) % &n
+            );
+        }
+    }
+
+    #[test]
+    fn reducing_numbers_longer_than_the_modulus() {
+        let mut rng = ChaChaRng::seed_from_u64(37);
+        for &bits in &[3, 64, 130] {
+            let n = rng.gen_biguint(bits) | BigUint::one() | (BigUint::one() << (bits - 1));
+            let mont = Montgomery::new(&n).unwrap();
+            for &x_bits in &[1, bits, 64 * 3 + 1, 5 * bits] {
+                let x = rng.gen_biguint(x_bits);
+                assert_eq!(mont.mul(&x, &BigUint::one()), &x % &n);
+            }
+        }
+    }
+
+    #[test]
+    fn swapping_limbs_conditionally() {
+        let (mut a, mut b) = (vec![1, 2, u64::MAX], vec![4, 5, 0]);
+        conditional_swap(&mut a, &mut b, 0);
+        assert_eq!((&a[..], &b[..]), (&[1, 2, u64::MAX][..], &[4, 5, 0][..]));
+        conditional_swap(&mut a, &mut b, 1);
+        assert_eq!((&a[..], &b[..]), (&[4, 5, 0][..], &[1, 2, u64::MAX][..]));
+    }
+
+    #[test]
+    fn exponentiating_matches_biguint() {
+        let mut rng = ChaChaRng::seed_from_u64(49);
+        let n = rng.gen_biguint(1024) | BigUint::one();
+        let mont = Montgomery::new(&n).unwrap();
+        for &exp_bits in &[0, 1, 2, 17, 64, 100, 300, 1023] {
+            let (base, exp) = (rng.gen_biguint(1100), rng.gen_biguint(exp_bits));
+            assert_eq!(mont.ladder_modpow(&base, &exp), base.modpow(&exp, &n));
+        }
+        assert_eq!(
+            mont.ladder_modpow(&BigUint::from(0u32), &BigUint::from(0u32)),
+            BigUint::one()
+        );
+    }
+}
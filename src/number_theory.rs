@@ -0,0 +1,319 @@
+use num::integer::{gcd, Integer};
+use num::traits::{One, Signed, ToPrimitive, Zero};
+use num_bigint_dig::{BigInt, BigUint, ToBigInt};
+use std::collections::HashMap;
+
+/*******************************
+ * GCD AND INVERSES
+ *******************************/
+
+/// Implements the extended euclidian algorithm.
+///
+/// Ie: it finds the greatest common divisor g (non-negative) of two integers *a* and *b*,
+/// and two integers *x* and *y* such that *ax* + *by* = g (Bézout coefficients).
+///
+/// Iterative, so that inputs as long as need be can't exhaust the stack.
+/// See: https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+pub fn egcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (BigInt::one(), BigInt::zero());
+    let (mut old_y, mut y) = (BigInt::zero(), BigInt::one());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        let next_r = &old_r - &q * &r;
+        old_r = std::mem::replace(&mut r, next_r);
+        let next_x = &old_x - &q * &x;
+        old_x = std::mem::replace(&mut x, next_x);
+        let next_y = &old_y - &q * &y;
+        old_y = std::mem::replace(&mut y, next_y);
+    }
+    match old_r.is_negative() {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// Calculates the modular multiplicative inverse *x* of an integer *a*,
+/// such that *ax* ≡ 1 (mod *m*), if *a* and *m* are coprime.
+///
+/// See: https://en.wikipedia.org/wiki/Modular_multiplicative_inverse
+pub fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m_ = m.to_bigint().unwrap();
+    let (g, x, _) = egcd(&(a % m).to_bigint().unwrap(), &m_);
+    match g.is_one() {
+        true => x.mod_floor(&m_).to_biguint(),
+        false => None,
+    }
+}
+
+/*******************************
+ * CHINESE REMAINDER THEOREM
+ *******************************/
+
+/// Finds the x such that x = a_i mod m_i for every (a_i, m_i) in `residues`, along with
+/// the modulus lcm(m_1, m_2, ...) it is unique modulo. The moduli need not be coprime,
+/// but then the residues must agree modulo their common factors, or there is no such x.
+///
+/// See: https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Generalization_to_non-coprime_moduli
+pub fn crt(residues: &[(BigUint, BigUint)]) -> Option<(BigUint, BigUint)> {
+    residues
+        .iter()
+        .try_fold((BigUint::zero(), BigUint::one()), |(x, m), (a, m_i)| {
+            let g = gcd(m.clone(), m_i.clone());
+            let (x_, a_) = (x.to_bigint().unwrap(), (a % m_i).to_bigint().unwrap());
+            let diff = a_ - &x_;
+            if !(&diff % g.to_bigint().unwrap()).is_zero() {
+                return None;
+            }
+            // x + m * t = a mod m_i, with t = (a - x) / g * inv(m / g) mod (m_i / g)
+            let m_i_g = m_i / &g;
+            let inv = mod_inverse(&(&m / &g), &m_i_g)?.to_bigint().unwrap();
+            let t = (diff / g.to_bigint().unwrap() * inv).mod_floor(&m_i_g.to_bigint().unwrap());
+            let lcm = &m * &m_i_g;
+            let x = (x_ + m.to_bigint().unwrap() * t).mod_floor(&lcm.to_bigint().unwrap());
+            Some((x.to_biguint().unwrap(), lcm))
+        })
+}
+
+/*******************************
+ * ROOTS
+ *******************************/
+
+/// The integer k-th root of `x`, ie: the largest r such that r^k <= x
+pub fn kth_root(x: &BigUint, k: u32) -> BigUint {
+    x.nth_root(k)
+}
+
+/// The k-th root of `x`, if `x` is a perfect k-th power
+pub fn exact_kth_root(x: &BigUint, k: u32) -> Option<BigUint> {
+    let r = kth_root(x, k);
+    match num::pow::pow(r.clone(), k as usize) == *x {
+        true => Some(r),
+        false => None,
+    }
+}
+
+/// The Jacobi symbol (a / n), for odd n: 0 if a and n share a factor, otherwise 1 or -1
+/// (and if n is prime, 1 if and only if a is a square mod n).
+///
+/// Panics if `n` is even.
+/// See: https://en.wikipedia.org/wiki/Jacobi_symbol#Calculating_the_Jacobi_symbol
+pub fn jacobi(a: &BigUint, n: &BigUint) -> i8 {
+    assert!(n.is_odd(), "The Jacobi symbol is only defined for odd n");
+    let (mut a, mut n) = (a % n, n.clone());
+    let mut symbol = 1;
+    while !a.is_zero() {
+        // (2 / n) = -1 iff n = 3 or 5 mod 8
+        while a.is_even() {
+            a >>= 1;
+            if [3u32, 5].contains(&low_bits(&n, 8)) {
+                symbol = -symbol;
+            }
+        }
+        // quadratic reciprocity: (a / n) = -(n / a) iff a = n = 3 mod 4
+        std::mem::swap(&mut a, &mut n);
+        if low_bits(&a, 4) == 3 && low_bits(&n, 4) == 3 {
+            symbol = -symbol;
+        }
+        a %= &n;
+    }
+    match n.is_one() {
+        true => symbol,
+        false => 0,
+    }
+}
+
+/// The Legendre symbol (a / p), for odd prime p: 0 if p divides a, 1 if a is a
+/// square mod p, and -1 otherwise
+pub fn legendre(a: &BigUint, p: &BigUint) -> i8 {
+    jacobi(a, p)
+}
+
+/// A square root of `a` modulo prime `p` (the other one being p minus it),
+/// if `a` is a square, found with the Tonelli-Shanks algorithm.
+///
+/// See: https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm
+pub fn sqrt_mod(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let a = a % p;
+    if a.is_zero() || p == &BigUint::from(2u32) {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    if low_bits(p, 4) == 3 {
+        return Some(a.modpow(&((p + 1u32) >> 2), p));
+    }
+
+    // p - 1 = q * 2^s, with q odd
+    let p_minus_1 = p - 1u32;
+    let s = p_minus_1.trailing_zeros().unwrap();
+    let q = &p_minus_1 >> s;
+    let z = (2u32..)
+        .map(BigUint::from)
+        .find(|z| legendre(z, p) == -1)
+        .unwrap();
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut r = a.modpow(&((&q + 1u32) >> 1), p);
+    while !t.is_one() {
+        // the least i such that t^(2^i) = 1
+        let mut i = 0;
+        let mut t_2i = t.clone();
+        while !t_2i.is_one() {
+            t_2i = &t_2i * &t_2i % p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        r = r * b % p;
+    }
+    Some(r)
+}
+
+fn low_bits(x: &BigUint, modulus: u32) -> u32 {
+    (x % modulus).to_u32().unwrap()
+}
+
+/*******************************
+ * DISCRETE LOGARITHMS
+ *******************************/
+
+/// Finds the least x < `bound` such that `g`^x = `h` mod `modulus`, if any, with Shanks'
+/// baby-step giant-step algorithm: in about sqrt(bound) multiplications, but as much memory.
+///
+/// See: https://en.wikipedia.org/wiki/Baby-step_giant-step
+pub fn discrete_log(g: &BigUint, h: &BigUint, modulus: &BigUint, bound: u64) -> Option<u64> {
+    let m = (bound as f64).sqrt().ceil() as u64;
+
+    // baby steps: g^j for j < m
+    let mut baby_steps = HashMap::new();
+    let mut g_j = BigUint::one() % modulus;
+    for j in 0..m {
+        baby_steps.entry(g_j.clone()).or_insert(j);
+        g_j = g_j * g % modulus;
+    }
+
+    // giant steps: h * g^(-m * i), which is some g^j iff h = g^(m * i + j)
+    let g_minus_m = mod_inverse(&g_j, modulus)?;
+    let mut gamma = h % modulus;
+    for i in 0..m {
+        if let Some(j) = baby_steps.get(&gamma) {
+            let x = i * m + j;
+            return match x < bound {
+                true => Some(x),
+                false => None,
+            };
+        }
+        gamma = gamma * &g_minus_m % modulus;
+    }
+    None
+}
+
+#[cfg(test)]
+mod number_theory_tests {
+    use super::*;
+
+    fn big(x: u64) -> BigUint {
+        BigUint::from(x)
+    }
+
+    #[test]
+    fn computing_bezout_coefficients() {
+        let (a, b) = (BigInt::from(240), BigInt::from(46));
+        let (g, x, y) = egcd(&a, &b);
+
+        assert_eq!(g, BigInt::from(2));
+        assert_eq!(&a * x + &b * y, g);
+        assert_eq!(egcd(&BigInt::from(-4), &BigInt::from(6)).0, BigInt::from(2));
+    }
+
+    #[test]
+    fn computing_modular_inverses() {
+        assert_eq!(mod_inverse(&big(17), &big(3120)), Some(big(2753)));
+        assert_eq!(mod_inverse(&big(3137), &big(3120)), Some(big(2753)));
+        assert_eq!(mod_inverse(&big(6), &big(9)), None);
+    }
+
+    #[test]
+    fn computing_modular_inverses_of_huge_numbers() {
+        // consecutive fibonacci numbers take the most steps (and used to overflow the stack)
+        let (mut a, mut b) = (BigUint::one(), BigUint::one());
+        for _ in 0..50_000 {
+            let next = &a + &b;
+            a = std::mem::replace(&mut b, next);
+        }
+        let inv = mod_inverse(&a, &b).unwrap();
+
+        assert!((a * inv % b).is_one());
+    }
+
+    #[test]
+    fn combining_residues_with_crt() {
+        let residues = [(big(2), big(3)), (big(3), big(5)), (big(2), big(7))];
+        assert_eq!(crt(&residues), Some((big(23), big(105))));
+
+        // non-coprime moduli
+        let residues = [(big(3), big(4)), (big(5), big(6))];
+        assert_eq!(crt(&residues), Some((big(11), big(12))));
+        let residues = [(big(3), big(4)), (big(4), big(6))];
+        assert_eq!(crt(&residues), None);
+
+        assert_eq!(crt(&[]), Some((big(0), big(1))));
+    }
+
+    #[test]
+    fn computing_kth_roots() {
+        let x = num::pow::pow(big(123_456_789), 5);
+        assert_eq!(kth_root(&x, 5), big(123_456_789));
+        assert_eq!(kth_root(&(&x - 1u32), 5), big(123_456_788));
+        assert_eq!(exact_kth_root(&x, 5), Some(big(123_456_789)));
+        assert_eq!(exact_kth_root(&(x + 1u32), 5), None);
+    }
+
+    #[test]
+    fn computing_jacobi_symbols() {
+        // the row for n = 45 in https://en.wikipedia.org/wiki/Jacobi_symbol#Table_of_values
+        let symbols = (1..=15)
+            .map(|a| jacobi(&big(a), &big(45)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            symbols,
+            vec![1, -1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, -1, 1, 0]
+        );
+
+        let squares_mod_11 = (1..11)
+            .filter(|&a| legendre(&big(a), &big(11)) == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(squares_mod_11, vec![1, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn computing_modular_square_roots() {
+        // p = 3 mod 4, p = 1 mod 4, and the P-224 prime, with p - 1 divisible by 2^96
+        let p_224 = (BigUint::one() << 224) - (BigUint::one() << 96) + 1u32;
+        for p in &[big(11), big(41), big(1_000_000_009), p_224] {
+            for a in 0..20u64 {
+                let a = big(a);
+                match sqrt_mod(&a, p) {
+                    Some(r) => assert_eq!(&r * &r % p, &a % p),
+                    None => assert_eq!(legendre(&a, p), -1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn computing_discrete_logs() {
+        let p = big(1_000_000_007);
+        let h = big(5).modpow(&big(123_456), &p);
+
+        assert_eq!(discrete_log(&big(5), &h, &p, 1 << 20), Some(123_456));
+        assert_eq!(discrete_log(&big(5), &h, &p, 100_000), None);
+        assert_eq!(discrete_log(&big(5), &big(1), &p, 10), Some(0));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::number_theory::jacobi;
+use num::integer::Integer;
+use num::traits::{One, Zero};
+use num_bigint_dig::{BigInt, BigUint, RandBigInt, Sign, ToBigInt};
+use rand::{CryptoRng, RngCore};
+
+lazy_static! {
+    /// the primes below 1000, to weed out most composites before any exponentiation
+    static ref SMALL_PRIMES: Vec<u32> = primal::Primes::all()
+        .take_while(|&p| p < 1000)
+        .map(|p| p as u32)
+        .collect();
+}
+
+/*******************************
+ * PRIMALITY TESTS
+ *******************************/
+
+/// Tests whether `n` is prime with the Baillie-PSW test: trial division by small primes,
+/// then a strong probable prime test to base 2 and a strong Lucas probable prime test.
+///
+/// Deterministic, and correct for every n below 2^64. No composite passing both tests is
+/// known at all (though they are conjectured to exist), as the few composites that are
+/// strong pseudoprimes to base 2 seem to be all but disjoint from strong Lucas pseudoprimes.
+/// See: https://en.wikipedia.org/wiki/Baillie%E2%80%93PSW_primality_test
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    match trial_division(n) {
+        Some(is_prime) => is_prime,
+        None => {
+            is_strong_probable_prime(n, &BigUint::from(2u32)) && is_strong_lucas_probable_prime(n)
+        }
+    }
+}
+
+/// Tests whether `n` is prime with `rounds` rounds of the Miller-Rabin test, to random bases.
+///
+/// A prime always passes. A composite passes each round with probability at most 1/4,
+/// so passes them all with probability at most 4^-rounds (and, for large random n, with
+/// much lower probability still: see https://doi.org/10.1090/S0025-5718-1993-1189518-9).
+pub fn miller_rabin<R: CryptoRng + RngCore>(rng: &mut R, n: &BigUint, rounds: usize) -> bool {
+    if let Some(is_prime) = trial_division(n) {
+        return is_prime;
+    }
+    let n_minus_1 = n - 1u32;
+    (0..rounds)
+        .all(|_| is_strong_probable_prime(n, &rng.gen_biguint_range(&2u32.into(), &n_minus_1)))
+}
+
+/// Some(whether `n` is prime) if trial division decides it, None otherwise
+fn trial_division(n: &BigUint) -> Option<bool> {
+    for &p in SMALL_PRIMES.iter() {
+        if n == &BigUint::from(p) {
+            return Some(true);
+        }
+        if (n % p).is_zero() {
+            return Some(false);
+        }
+    }
+    match n < &BigUint::from(2u32) {
+        true => Some(false),
+        false => None,
+    }
+}
+
+/// Whether odd `n` is a strong probable prime to `base`: with n - 1 = d * 2^s (d odd),
+/// either base^d = 1 or base^(d * 2^r) = -1 mod n for some r < s
+fn is_strong_probable_prime(n: &BigUint, base: &BigUint) -> bool {
+    let n_minus_1 = n - 1u32;
+    let s = n_minus_1.trailing_zeros().unwrap();
+    let d = &n_minus_1 >> s;
+    let mut x = base.modpow(&d, n);
+    if x.is_one() || x == n_minus_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n_minus_1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether odd `n` (not a perfect square) is a strong Lucas probable prime, with the
+/// parameters of Selfridge's method A: D the first of 5, -7, 9, -11, ... for which the
+/// Jacobi symbol (D / n) = -1, P = 1 and Q = (1 - D) / 4.
+///
+/// With n + 1 = d * 2^s (d odd), either U_d = 0 or V_(d * 2^r) = 0 mod n for some r < s.
+/// See: https://en.wikipedia.org/wiki/Lucas_pseudoprime#Strong_Lucas_pseudoprimes
+fn is_strong_lucas_probable_prime(n: &BigUint) -> bool {
+    let n_ = n.to_bigint().unwrap();
+    let mut d = BigInt::from(5);
+    loop {
+        match jacobi(&d.mod_floor(&n_).to_biguint().unwrap(), n) {
+            -1 => break,
+            // d shares a factor with n (which trial division has made larger than d)
+            0 => return false,
+            _ => (),
+        }
+        // only a perfect square could keep this going: check, after a few attempts
+        if d == BigInt::from(-15) && &n.sqrt() * &n.sqrt() == *n {
+            return false;
+        }
+        d = match d.sign() {
+            Sign::Minus => -d + 2,
+            _ => -d - 2,
+        };
+    }
+    let q: BigInt = (BigInt::one() - &d) / 4;
+
+    let reduce = |x: BigInt| x.mod_floor(&n_);
+    // halves x mod n (n being odd, x + n is even when x is odd)
+    let halve = |x: BigInt| reduce(if x.is_odd() { x + &n_ } else { x } >> 1);
+
+    let n_plus_1 = n + 1u32;
+    let s = n_plus_1.trailing_zeros().unwrap();
+    let k = &n_plus_1 >> s;
+
+    // U_k, V_k and Q^k, from the leading bit of k down, with (for P = 1):
+    // U_2k = U_k * V_k, V_2k = V_k^2 - 2 * Q^k, U_k+1 = (U_k + V_k) / 2, V_k+1 = (D * U_k + V_k) / 2
+    let (mut u, mut v, mut q_k) = (BigInt::one(), BigInt::one(), reduce(q.clone()));
+    for i in (0..k.bits() - 1).rev() {
+        u = reduce(&u * &v);
+        v = reduce(&v * &v - &q_k * 2);
+        q_k = reduce(&q_k * &q_k);
+        if (&k >> i).is_odd() {
+            let u_ = halve(&u + &v);
+            v = halve(&d * &u + &v);
+            u = u_;
+            q_k = reduce(&q_k * &q);
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = reduce(&v * &v - &q_k * 2);
+        q_k = reduce(&q_k * &q_k);
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/*******************************
+ * PRIME GENERATION
+ *******************************/
+
+/// Generates a random prime of exactly `bits` bits (at least 2)
+pub fn gen_prime<R: CryptoRng + RngCore>(rng: &mut R, bits: usize) -> BigUint {
+    let top_bit = BigUint::one() << (bits - 1);
+    loop {
+        let candidate = rng.gen_biguint(bits) | &top_bit | BigUint::one();
+        if is_probable_prime(&candidate) {
+            break candidate;
+        }
+    }
+}
+
+/// The smallest prime greater than `n`
+pub fn next_prime(n: &BigUint) -> BigUint {
+    if n < &BigUint::from(2u32) {
+        return BigUint::from(2u32);
+    }
+    // the next odd number, then every other one
+    let mut candidate = (n + 1u32) | BigUint::one();
+    while !is_probable_prime(&candidate) {
+        candidate += 2u32;
+    }
+    candidate
+}
+
+/// Generates a random safe prime p of exactly `bits` bits (at least 3), ie: a prime such
+/// that q = (p - 1) / 2 is prime too, so that the multiplicative group mod p has no small
+/// subgroups but {1, p - 1} (as wanted for Diffie-Hellman).
+///
+/// Safe primes are rare (about 1 in bits^2 numbers), so this takes a while for large `bits`.
+/// See: https://en.wikipedia.org/wiki/Safe_and_Sophie_Germain_primes
+pub fn gen_safe_prime<R: CryptoRng + RngCore>(rng: &mut R, bits: usize) -> BigUint {
+    let top_bit = BigUint::one() << (bits - 2);
+    loop {
+        let q = rng.gen_biguint(bits - 1) | &top_bit | BigUint::one();
+        let p: BigUint = (&q << 1) + 1u32;
+        // both are at least cheaply checked before either is tested in earnest
+        if trial_division(&q) != Some(false)
+            && trial_division(&p) != Some(false)
+            && is_probable_prime(&q)
+            && is_probable_prime(&p)
+        {
+            break p;
+        }
+    }
+}
+
+/// Generates DSA-style domain parameters: a prime q of exactly `q_bits` bits, and a prime p
+/// of exactly `p_bits` bits such that q divides p - 1 (so that the multiplicative group mod
+/// p has a subgroup of order q). Eg: the (2048, 256) sizes of FIPS 186-4.
+///
+/// Follows the outline (though not the seeded, verifiable procedure) of:
+/// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.186-4.pdf#page=47
+pub fn gen_dsa_params<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    p_bits: usize,
+    q_bits: usize,
+) -> (BigUint, BigUint) {
+    assert!(q_bits < p_bits, "q must be shorter than p");
+    let q = gen_prime(rng, q_bits);
+    let two_q = &q << 1;
+    let top_bit = BigUint::one() << (p_bits - 1);
+    loop {
+        // the p_bits-bit p = 1 mod 2q just below a random p_bits-bit x
+        let x = rng.gen_biguint(p_bits) | &top_bit;
+        let p = &x - (&x % &two_q) + 1u32;
+        if p.bits() == p_bits && is_probable_prime(&p) {
+            break (p, q);
+        }
+    }
+}
+
+#[cfg(test)]
+mod primality_tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn big(x: u64) -> BigUint {
+        BigUint::from(x)
+    }
+
+    #[test]
+    fn telling_primes_from_composites_below_100_000() {
+        let primes = primal::Sieve::new(100_000);
+        for n in 0..100_000 {
+            assert_eq!(
+                is_probable_prime(&big(n as u64)),
+                primes.is_prime(n),
+                "{}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn rejecting_pseudoprimes() {
+        // Carmichael numbers
+        for &n in &[561u64, 41_041, 825_265, 321_197_185, 9_746_347_772_161] {
+            assert!(!is_probable_prime(&big(n)), "{}", n);
+        }
+        // strong pseudoprimes to base 2, which a single round of Miller-Rabin may miss
+        for &n in &[2047u64, 3277, 4033, 4681, 8321, 3_215_031_751] {
+            assert!(is_strong_probable_prime(&big(n), &big(2)));
+            assert!(!is_probable_prime(&big(n)), "{}", n);
+        }
+        // strong Lucas pseudoprimes
+        for &n in &[5459u64, 5777, 10_877, 16_109, 18_971] {
+            assert!(is_strong_lucas_probable_prime(&big(n)));
+            assert!(!is_probable_prime(&big(n)), "{}", n);
+        }
+        // squares of the Wieferich primes 1093 and 3511 are strong pseudoprimes to base 2,
+        // and (being squares) have no Selfridge D
+        for &n in &[1_194_649u64, 12_327_121] {
+            assert!(is_strong_probable_prime(&big(n), &big(2)));
+            assert!(!is_probable_prime(&big(n)), "{}", n);
+        }
+    }
+
+    #[test]
+    fn accepting_large_primes() {
+        let mut rng = OsRng::new().unwrap();
+        let m_127 = (BigUint::one() << 127) - 1u32;
+        let m_521 = (BigUint::one() << 521) - 1u32;
+
+        for p in &[&m_127, &m_521] {
+            assert!(is_probable_prime(p));
+            assert!(miller_rabin(&mut rng, p, 20));
+        }
+        assert!(!is_probable_prime(&(&m_127 * &m_127)));
+        assert!(!miller_rabin(&mut rng, &(&m_127 * &m_521), 20));
+    }
+
+    #[test]
+    fn finding_next_primes() {
+        let primes = primal::Primes::all().take(1000).collect::<Vec<_>>();
+        for n in 0..primes[999] {
+            let next = primes.iter().find(|&&p| p > n).unwrap();
+            assert_eq!(next_prime(&big(n as u64)), big(*next as u64), "{}", n);
+        }
+        // after a maximal prime gap, of 354 (cf: https://oeis.org/A002386)
+        assert_eq!(next_prime(&big(4_302_407_359)), big(4_302_407_713));
+    }
+
+    #[test]
+    fn generating_safe_primes() {
+        let mut rng = OsRng::new().unwrap();
+        let p = gen_safe_prime(&mut rng, 128);
+
+        assert_eq!(p.bits(), 128);
+        assert!(is_probable_prime(&p));
+        assert!(is_probable_prime(&(p >> 1)));
+    }
+
+    #[test]
+    fn generating_dsa_params() {
+        let mut rng = OsRng::new().unwrap();
+        let (p, q) = gen_dsa_params(&mut rng, 512, 160);
+
+        assert_eq!((p.bits(), q.bits()), (512, 160));
+        assert!(is_probable_prime(&p) && is_probable_prime(&q));
+        assert!(((p - 1u32) % q).is_zero());
+    }
+}
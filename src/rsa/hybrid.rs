@@ -0,0 +1,54 @@
+use crate::bytes::Bytes;
+use crate::hashing::{self, HashFunction};
+use crate::xor_cypher;
+use num_bigint_dig::BigUint;
+
+/// length (in bytes) of the symmetric keys wrapped under RSA
+pub(super) const KEY_LEN: usize = 32;
+
+/// A message encrypted by `rsa::encrypt_hybrid`: a fresh symmetric key, encrypted
+/// under RSA-OAEP, and the message itself, encrypted under that key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridCyphertext {
+    pub wrapped_key: BigUint,
+    pub payload: Bytes,
+}
+
+/// Encrypts (or, equivalently, decrypts) `data` by XOR-ing it with a keystream
+/// derived from `key` by hashing it together with a 4-byte big-endian counter
+/// (ie: SHA-256 in counter mode, built from MGF1).
+///
+/// There is no nonce, so a key must never be used for more than one message:
+/// two payloads encrypted under the same key XOR to the XOR of their cleartexts.
+/// Nor is there a MAC: a tampered payload decrypts to (correspondingly tampered) garbage.
+pub(super) fn apply_keystream(key: &[u8], data: &[u8]) -> Bytes {
+    xor_cypher::xor(data, hashing::mgf1(HashFunction::Sha256, key, data.len()))
+}
+
+#[cfg(test)]
+mod hybrid_tests {
+    use super::*;
+
+    #[test]
+    fn applying_keystream_twice_yields_cleartext() {
+        let key = [0x42; KEY_LEN];
+        let cyphertext = apply_keystream(&key, b"hello world i am here");
+
+        assert_ne!(cyphertext, Bytes::from("hello world i am here"));
+        assert_eq!(
+            apply_keystream(&key, &cyphertext),
+            Bytes::from("hello world i am here")
+        );
+    }
+
+    #[test]
+    fn applying_keystreams_of_different_keys() {
+        let m = vec![0; 100];
+        let (c1, c2) = (
+            apply_keystream(&[0x01; KEY_LEN], &m),
+            apply_keystream(&[0x02; KEY_LEN], &m),
+        );
+        assert_eq!(c1.len(), 100);
+        assert_ne!(c1, c2);
+    }
+}
@@ -0,0 +1,1110 @@
+use crate::hashing::HashFunction;
+use crate::montgomery::Montgomery;
+use crate::number_theory::mod_inverse;
+use crate::primality;
+use bigint::{BigUint, RandBigInt};
+use num::integer::{gcd, lcm, Integer};
+use num::traits::{One, Zero};
+use num_bigint_dig as bigint;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+mod der;
+mod hybrid;
+mod oaep;
+mod pem;
+pub(crate) mod pkcs1v15;
+mod pss;
+
+pub use hybrid::HybridCyphertext;
+pub use oaep::OaepParams;
+pub use pss::PssParams;
+
+/// public exponent used by `gen_keypair` (the fourth Fermat number, 2^16 + 1)
+pub const DEFAULT_EXPONENT: u32 = 65_537;
+
+/// smallest modulus size (in bits) that `gen_keypair` will generate: just long enough
+/// for the public exponent (`DEFAULT_EXPONENT`, of 17 bits) to be shorter than `n`
+pub const MIN_KEYSIZE: usize = 18;
+
+/// largest public exponent size (in bits) that `gen_keypair_with_exponent` will accept,
+/// as per FIPS 186-4 (appendix B.3.1): e < 2^256
+pub const MAX_EXPONENT_BITS: usize = 256;
+
+pub struct PublicKey {
+    pub e: BigUint,
+    pub n: BigUint,
+}
+
+/// A private key, holding (in addition to the private exponent `d`) the prime
+/// factors of `n` and the values derived from them that are used to perform private
+/// key operations via the Chinese Remainder Theorem, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-3.2
+pub struct SecretKey {
+    pub d: BigUint,
+    pub n: BigUint,
+    pub lambda_n: BigUint,
+    /// the public exponent, kept to check the results of private key operations
+    pub e: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+    /// `d` mod (`p` - 1)
+    pub d_p: BigUint,
+    /// `d` mod (`q` - 1)
+    pub d_q: BigUint,
+    /// `q`^-1 mod `p`
+    pub q_inv: BigUint,
+}
+
+impl SecretKey {
+    /// Builds the secret key corresponding to primes `p`, `q` and public exponent `e`
+    /// (or `None` if `p` = `q` or `e` has no inverse mod lambda(`p` * `q`))
+    pub fn from_primes(p: BigUint, q: BigUint, e: BigUint) -> Option<SecretKey> {
+        if p == q {
+            return None;
+        }
+        let lambda_n = lcm(&p - BigUint::one(), &q - BigUint::one());
+        let d = mod_inverse(&e, &lambda_n)?;
+        let q_inv = mod_inverse(&q, &p)?;
+        Some(SecretKey {
+            n: &p * &q,
+            d_p: &d % (&p - BigUint::one()),
+            d_q: &d % (&q - BigUint::one()),
+            d,
+            lambda_n,
+            e,
+            p,
+            q,
+            q_inv,
+        })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            e: self.e.clone(),
+            n: self.n.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// the message is too long to be encoded for the key's modulus
+    MessageTooLong,
+    /// the cyphertext could not be decrypted (deliberately uninformative, see `oaep::decode`)
+    Decryption,
+    /// the key's modulus is too short to hold the encoded digest (and salt, for PSS)
+    ModulusTooShort,
+    InvalidSignature,
+    /// the requested modulus size is below `MIN_KEYSIZE`
+    KeySizeTooSmall,
+    /// the public exponent is even, less than 3, or too large (for `MAX_EXPONENT_BITS` or
+    /// the requested modulus size), so no key can be built around it
+    InvalidExponent,
+    /// no primes suitable for the public exponent were found in a bounded search, though
+    /// the parameters themselves are valid (see `gen_prime`)
+    KeyGeneration,
+    /// a key could not be decoded from DER or PEM
+    InvalidKeyEncoding,
+    /// a private key operation produced a result inconsistent with the public key
+    /// (eg: due to a hardware fault), and was withheld rather than leak the key
+    Fault,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MessageTooLong => write!(f, "message too long"),
+            Error::Decryption => write!(f, "decryption error"),
+            Error::ModulusTooShort => write!(f, "modulus too short"),
+            Error::InvalidSignature => write!(f, "invalid signature"),
+            Error::KeySizeTooSmall => write!(f, "key size too small"),
+            Error::InvalidExponent => write!(f, "invalid public exponent"),
+            Error::KeyGeneration => write!(f, "no suitable primes found"),
+            Error::InvalidKeyEncoding => write!(f, "invalid key encoding"),
+            Error::Fault => write!(f, "private key operation failed consistency check"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*******************
+ * PUBLIC FUNCTIONS
+ *******************/
+
+/// Generates a keypair with a modulus of exactly `keysize` bits and `DEFAULT_EXPONENT`
+/// as public exponent.
+///
+/// Panics if `keysize` is below `MIN_KEYSIZE`.
+pub fn gen_keypair(keysize: usize) -> (PublicKey, SecretKey) {
+    gen_keypair_with_exponent(keysize, &BigUint::from(DEFAULT_EXPONENT))
+        .expect("Failed to generate keypair")
+}
+
+/// Generates a keypair with a modulus of exactly `keysize` bits and public exponent `e`,
+/// drawing primes from the operating system's RNG.
+///
+/// `e` is used as given: rather than adjusting `e` to suit the primes, primes `p` for
+/// which `e` is not coprime to `p` - 1 are discarded. `e` must therefore be odd (and at
+/// least 3), or no suitable primes exist. It must also be shorter than both the modulus
+/// and `MAX_EXPONENT_BITS`, or fails with `Error::InvalidExponent`. Fails with
+/// `Error::KeyGeneration` if (as for small keys and an `e` with many small factors)
+/// suitable primes still can't be found.
+pub fn gen_keypair_with_exponent(
+    keysize: usize,
+    e: &BigUint,
+) -> Result<(PublicKey, SecretKey), Error> {
+    let mut rng = OsRng::new().expect("Failed to build RNG");
+    gen_keypair_with_rng(&mut rng, keysize, e)
+}
+
+/// Generates a keypair as `gen_keypair_with_exponent` does, drawing primes from `rng`.
+///
+/// Given a seeded RNG, the same keypair is generated every time (eg: to reproduce a test
+/// case or an attack scenario).
+pub fn gen_keypair_with_rng<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    keysize: usize,
+    e: &BigUint,
+) -> Result<(PublicKey, SecretKey), Error> {
+    if keysize < MIN_KEYSIZE {
+        return Err(Error::KeySizeTooSmall);
+    }
+    if e < &BigUint::from(3u32)
+        || e.is_even()
+        || e.bits() > MAX_EXPONENT_BITS
+        || e.bits() >= keysize
+    {
+        return Err(Error::InvalidExponent);
+    }
+    let (p, q) = gen_distinct_primes(rng, keysize, e)?;
+    let sk = SecretKey::from_primes(p, q, e.clone()).expect("e is coprime to p - 1 and q - 1");
+    Ok((sk.public_key(), sk))
+}
+
+/// Encrypts `m` using RSAES-OAEP with the default parameters (see `OaepParams`)
+pub fn encrypt(m: &[u8], pk: &PublicKey) -> Result<BigUint, Error> {
+    encrypt_with(m, pk, &OaepParams::default())
+}
+
+/// Decrypts `c` using RSAES-OAEP with the default parameters (see `OaepParams`)
+pub fn decrypt(c: &BigUint, sk: &SecretKey) -> Result<Vec<u8>, Error> {
+    decrypt_with(c, sk, &OaepParams::default())
+}
+
+/// Encrypts `m` using RSAES-OAEP as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.1.1
+///
+/// Fails with `Error::MessageTooLong` if `m` is longer than `params.max_message_len(k)`
+/// (for a modulus of `k` bytes), rather than reducing it mod `n`: for longer messages,
+/// use `encrypt_hybrid`.
+pub fn encrypt_with(
+    m: &[u8],
+    PublicKey { e, n }: &PublicKey,
+    params: &OaepParams,
+) -> Result<BigUint, Error> {
+    Ok(encode(m, byte_len(n), params)?.modpow(e, n))
+}
+
+/// Decrypts `c` using RSAES-OAEP as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.1.2
+pub fn decrypt_with(c: &BigUint, sk: &SecretKey, params: &OaepParams) -> Result<Vec<u8>, Error> {
+    decode(&decrypt_crt(c, sk)?, byte_len(&sk.n), params)
+}
+
+/// Encrypts `m` (of any length) by generating a random symmetric key, encrypting `m`
+/// under that key with a stream cipher (see `hybrid::apply_keystream`) and encrypting the
+/// key itself using RSAES-OAEP with the default parameters.
+///
+/// Fails with `Error::ModulusTooShort` if the modulus cannot hold an OAEP-encoded key
+/// (ie: is shorter than 74 bytes).
+pub fn encrypt_hybrid(m: &[u8], pk: &PublicKey) -> Result<HybridCyphertext, Error> {
+    let mut key = [0; hybrid::KEY_LEN];
+    OsRng::new()
+        .expect("Failed to build RNG")
+        .fill_bytes(&mut key);
+    let wrapped_key = encrypt(&key, pk).map_err(|err| match err {
+        Error::MessageTooLong => Error::ModulusTooShort,
+        err => err,
+    })?;
+    Ok(HybridCyphertext {
+        wrapped_key,
+        payload: hybrid::apply_keystream(&key, m),
+    })
+}
+
+/// Decrypts a cyphertext produced by `encrypt_hybrid`
+pub fn decrypt_hybrid(c: &HybridCyphertext, sk: &SecretKey) -> Result<Vec<u8>, Error> {
+    let key = decrypt(&c.wrapped_key, sk)?;
+    if key.len() != hybrid::KEY_LEN {
+        return Err(Error::Decryption);
+    }
+    Ok(hybrid::apply_keystream(&key, &c.payload).into_vec())
+}
+
+/// Encrypts integer `m` with no padding at all (ie: "textbook" RSA, or the RSAEP primitive
+/// specified in: https://tools.ietf.org/html/rfc8017#section-5.1.1)
+///
+/// This is deterministic and malleable: it only exists to build (and attack) other schemes.
+pub fn encrypt_unpadded(m: &BigUint, PublicKey { e, n }: &PublicKey) -> Result<BigUint, Error> {
+    if m >= n {
+        return Err(Error::MessageTooLong);
+    }
+    Ok(m.modpow(e, n))
+}
+
+/// Decrypts integer `c` with no padding at all (ie: the RSADP primitive specified in:
+/// https://tools.ietf.org/html/rfc8017#section-5.1.2)
+pub fn decrypt_unpadded(c: &BigUint, sk: &SecretKey) -> Result<BigUint, Error> {
+    decrypt_crt(c, sk)
+}
+
+/// Encrypts `m` using RSAES-PKCS1-v1_5, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.2.1
+///
+/// Only for interoperating with legacy systems: prefer `encrypt` (OAEP) otherwise.
+pub fn encrypt_pkcs1v15(m: &[u8], PublicKey { e, n }: &PublicKey) -> Result<BigUint, Error> {
+    let mut rng = OsRng::new().expect("Failed to build RNG");
+    let em = pkcs1v15::encode(m, byte_len(n), &mut rng)?;
+    Ok(encode_os2ip(&em).modpow(e, n))
+}
+
+/// Decrypts `c` using RSAES-PKCS1-v1_5, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.2.2
+pub fn decrypt_pkcs1v15(c: &BigUint, sk: &SecretKey) -> Result<Vec<u8>, Error> {
+    pkcs1v15::decode(&decode_i2osp(&decrypt_crt(c, sk)?, byte_len(&sk.n)))
+}
+
+/// Signs (a `hash` digest of) `m` using RSASSA-PKCS1-v1_5, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-8.2.1
+pub fn sign_pkcs1v15(m: &[u8], sk: &SecretKey, hash: HashFunction) -> Result<BigUint, Error> {
+    let em = pkcs1v15::encode_signature(m, byte_len(&sk.n), hash)?;
+    decrypt_crt(&encode_os2ip(&em), sk)
+}
+
+/// Verifies an RSASSA-PKCS1-v1_5 signature `s` of `m`, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-8.2.2
+///
+/// The whole encoded block is rebuilt from `m` and compared, rather than parsed
+/// (which is how sloppy verifiers end up accepting forgeries, cf: Bleichenbacher '06).
+pub fn verify_pkcs1v15(
+    m: &[u8],
+    s: &BigUint,
+    PublicKey { e, n }: &PublicKey,
+    hash: HashFunction,
+) -> Result<(), Error> {
+    if s >= n {
+        return Err(Error::InvalidSignature);
+    }
+    let em = decode_i2osp(&s.modpow(e, n), byte_len(n));
+    match pkcs1v15::encode_signature(m, byte_len(n), hash)? == em {
+        true => Ok(()),
+        false => Err(Error::InvalidSignature),
+    }
+}
+
+/// Signs `m` using RSASSA-PSS, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-8.1.1
+pub fn sign_pss(m: &[u8], sk: &SecretKey, params: &PssParams) -> Result<BigUint, Error> {
+    let mut salt = vec![0; params.salt_len];
+    OsRng::new()
+        .expect("Failed to build RNG")
+        .fill_bytes(&mut salt);
+    let em = pss::encode(m, sk.n.bits() - 1, params, &salt)?;
+    decrypt_crt(&encode_os2ip(&em), sk)
+}
+
+/// Verifies an RSASSA-PSS signature `s` of `m`, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-8.1.2
+pub fn verify_pss(
+    m: &[u8],
+    s: &BigUint,
+    PublicKey { e, n }: &PublicKey,
+    params: &PssParams,
+) -> Result<(), Error> {
+    if s >= n {
+        return Err(Error::InvalidSignature);
+    }
+    let em_bits = n.bits() - 1;
+    let m_int = s.modpow(e, n);
+    if m_int.bits() > em_bits {
+        return Err(Error::InvalidSignature);
+    }
+    pss::verify(
+        m,
+        &decode_i2osp(&m_int, em_bits.div_ceil(8)),
+        em_bits,
+        params,
+    )
+}
+
+/*******************
+ * HELPER FUNCTIONS
+ *******************/
+
+/// Raises `c` to the private exponent (ie: RSADP / RSASP1) via the Chinese Remainder
+/// Theorem, as specified in: https://tools.ietf.org/html/rfc8017#section-5.1.2
+///
+/// Exponentiating mod `p` and `q` separately with half-size exponents is ~4x faster than
+/// `c.modpow(d, n)`. But a fault in either half yields a result that reveals the factors of `n`
+/// (cf: Boneh, DeMillo & Lipton '97), so the result is checked against the public exponent
+/// before being released.
+///
+/// To keep timing from revealing anything about `c` or the key, `c` is blinded with a fresh
+/// random factor before exponentiation (and the result unblinded after), and each half is
+/// computed with `Montgomery::ladder_modpow`, whose sequence of operations doesn't depend on
+/// the exponent's bits (or its length), and whose multiplications don't depend on their operands.
+fn decrypt_crt(c: &BigUint, sk: &SecretKey) -> Result<BigUint, Error> {
+    if c >= &sk.n {
+        return Err(Error::Decryption);
+    }
+    let mut rng = OsRng::new().expect("Failed to build RNG");
+    let (r, r_inv) = gen_blinding_factor(&mut rng, &sk.n);
+    let blinded = (c * r.modpow(&sk.e, &sk.n)) % &sk.n;
+
+    let m_p = ladder_modpow(&blinded, &sk.d_p, &sk.p).ok_or(Error::Decryption)?;
+    let m_q = ladder_modpow(&blinded, &sk.d_q, &sk.q).ok_or(Error::Decryption)?;
+    let m = (combine_crt(&m_p, &m_q, sk) * r_inv) % &sk.n;
+    match &m.modpow(&sk.e, &sk.n) == c {
+        true => Ok(m),
+        false => Err(Error::Fault),
+    }
+}
+
+/// Generates a random `r` invertible mod `n`, and its inverse. Decrypting `c` * `r`^e
+/// yields `m` * `r`, so the private key is never applied to an attacker-chosen value
+/// (cf: Kocher '96, https://www.paulkocher.com/doc/TimingAttacks.pdf, section 10).
+fn gen_blinding_factor<R: CryptoRng + RngCore>(rng: &mut R, n: &BigUint) -> (BigUint, BigUint) {
+    loop {
+        let r = rng.gen_biguint_below(n);
+        if let Some(r_inv) = mod_inverse(&r, n) {
+            break (r, r_inv);
+        }
+    }
+}
+
+/// Computes `base`^`exp` mod `modulus` with `Montgomery::ladder_modpow` (or `None` if
+/// `modulus` is even). The context is rebuilt on every call, which costs far less than the
+/// exponentiation, so that it can never disagree with the key's (public) primes.
+fn ladder_modpow(base: &BigUint, exp: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    Montgomery::new(modulus).map(|mont| mont.ladder_modpow(base, exp))
+}
+
+/// Given `m_p` = m mod `p` and `m_q` = m mod `q`, recovers m mod `n` using Garner's formula:
+/// m = `m_q` + `q` * (`q_inv` * (`m_p` - `m_q`) mod `p`)
+pub(crate) fn combine_crt(m_p: &BigUint, m_q: &BigUint, sk: &SecretKey) -> BigUint {
+    // add a multiple of p to keep the difference non-negative
+    let diff = (m_p + &sk.p - (m_q % &sk.p)) % &sk.p;
+    let h = (&sk.q_inv * diff) % &sk.p;
+    m_q + &sk.q * h
+}
+
+/// Generates two primes suitable for a modulus of exactly `keysize` bits (see `gen_prime`)
+/// that are far enough apart for `n` not to be factored by Fermat's method, ie:
+/// |p - q| > 2^(`keysize` / 2 - 100), as required by FIPS 186-4 (appendix B.3.3).
+/// For small keys the bound degenerates to `p` ≠ `q`.
+///
+/// Fails with `Error::KeyGeneration` if `gen_prime` finds no prime (see below).
+fn gen_distinct_primes<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    keysize: usize,
+    e: &BigUint,
+) -> Result<(BigUint, BigUint), Error> {
+    let min_distance = BigUint::one() << (keysize / 2).saturating_sub(100);
+    loop {
+        let p = gen_prime(rng, keysize - keysize / 2, e).ok_or(Error::KeyGeneration)?;
+        let q = gen_prime(rng, keysize / 2, e).ok_or(Error::KeyGeneration)?;
+        let distance = if p > q { &p - &q } else { &q - &p };
+        if distance > min_distance {
+            break Ok((p, q));
+        }
+    }
+}
+
+/// Generates a prime of exactly `bits` bits, such that `p` - 1 is coprime to `e`.
+///
+/// The two most significant bits are set, so that the product of two such primes of
+/// `a` and `b` bits has exactly `a` + `b` bits (since 1.5^2 > 2).
+///
+/// About one in `bits` candidates is prime, so gives up (returning `None`) after 100
+/// times that many: for most `e` that is all but impossible, but for an `e` sharing a
+/// factor with every `p` - 1 of the requested size, no candidate will ever do.
+fn gen_prime<R: CryptoRng + RngCore>(rng: &mut R, bits: usize, e: &BigUint) -> Option<BigUint> {
+    let top_bits = BigUint::from(3u32) << (bits - 2);
+    (0..100 * bits)
+        .map(|_| rng.gen_biguint(bits) | &top_bits | BigUint::one())
+        .find(|candidate| {
+            primality::is_probable_prime(candidate)
+                && gcd(candidate - BigUint::one(), e.clone()).is_one()
+        })
+}
+
+/// Encodes a byte array as an integer, with appropriate padding and hashing,
+/// according to EME-OAEP encoding specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.1
+fn encode(m: &[u8], k: usize, params: &OaepParams) -> Result<BigUint, Error> {
+    let mut seed = vec![0; params.hash.output_len()];
+    OsRng::new()
+        .expect("Failed to build RNG")
+        .fill_bytes(&mut seed);
+    oaep::encode(m, k, params, &seed).map(|em| encode_os2ip(&em))
+}
+
+/// Encodes a byte array as an integer as specified in RFC 8017's OS2IP encoding:
+/// https://tools.ietf.org/html/rfc8017#section-4.1
+pub(crate) fn encode_os2ip(bytes: &[u8]) -> BigUint {
+    BigUint::from_radix_be(bytes, 256).unwrap()
+}
+
+/// Decodes a byte array from an integer, reversing the padding and hashing
+/// of EME-OAEP encoding specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.1
+fn decode(int: &BigUint, k: usize, params: &OaepParams) -> Result<Vec<u8>, Error> {
+    oaep::decode(&decode_i2osp(int, k), params)
+}
+
+/// Decodes a byte array of length `x_len` from an integer as specified in RFC 8017's
+/// I2OSP encoding: https://tools.ietf.org/html/rfc8017#section-4.1
+/// (callers must ensure `int` < 256^`x_len`, which always holds when `int` < `n`
+/// and `x_len` is the byte length of `n`)
+pub(crate) fn decode_i2osp(int: &BigUint, x_len: usize) -> Vec<u8> {
+    let bytes = match int.is_zero() {
+        true => vec![],
+        false => int.to_bytes_be(),
+    };
+    let mut padded = vec![0; x_len - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
+
+/// `0x01` if `b` is zero, `0x00` otherwise, computed without branching
+/// (for padding checks that must not leak which byte failed through timing)
+fn ct_is_zero(b: u8) -> u8 {
+    (((b as u16).wrapping_sub(1) >> 8) & 1) as u8
+}
+
+/// Length in bytes of the modulus `n` (called `k` in RFC 8017)
+pub(crate) fn byte_len(n: &BigUint) -> usize {
+    n.bits().div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn generating_keypair() {
+        let (
+            PublicKey { e, n: n1 },
+            SecretKey {
+                d, n: n2, lambda_n, ..
+            },
+        ) = gen_keypair(64);
+        assert_eq!(n1, n2);
+        assert_eq!(e, BigUint::from(DEFAULT_EXPONENT));
+        assert_eq!((e * d) % lambda_n, BigUint::one());
+    }
+
+    #[test]
+    fn generating_keypairs_of_exact_size() {
+        for &keysize in &[18, 19, 64, 127, 512] {
+            let (pk, sk) = gen_keypair(keysize);
+            assert_eq!(pk.n.bits(), keysize);
+            assert_eq!(sk.p.bits(), keysize - keysize / 2);
+            assert_eq!(sk.q.bits(), keysize / 2);
+        }
+    }
+
+    #[test]
+    fn generating_keypair_with_chosen_exponent() {
+        let (pk, sk) = gen_keypair_with_exponent(512, &BigUint::from(3u32)).unwrap();
+        assert_eq!(pk.e, BigUint::from(3u32));
+        assert_eq!((&sk.e * &sk.d) % &sk.lambda_n, BigUint::one());
+
+        let c = BigUint::from(42u32).modpow(&pk.e, &pk.n);
+        assert_eq!(decrypt_crt(&c, &sk).unwrap(), BigUint::from(42u32));
+    }
+
+    // known answer: primes confirmed with `openssl prime`
+    #[test]
+    fn generating_keypair_from_seeded_rng() {
+        let e = BigUint::from(DEFAULT_EXPONENT);
+        let (_, sk) = gen_keypair_with_rng(&mut ChaChaRng::seed_from_u64(20), 256, &e).unwrap();
+
+        assert_eq!(sk.p, hex_to_int("e9a74e0d5f723dc3485002a7a1bf4ebf"));
+        assert_eq!(sk.q, hex_to_int("edca1e12597e2f383616c24ddf0ccc9d"));
+        assert_eq!(sk.n.bits(), 256);
+    }
+
+    #[test]
+    fn generating_the_same_keypair_from_the_same_seed() {
+        let e = BigUint::from(3u32);
+        let keypair = |seed| {
+            let mut rng = ChaChaRng::seed_from_u64(seed);
+            gen_keypair_with_rng(&mut rng, 512, &e).unwrap().1
+        };
+        let (sk1, sk2, sk3) = (keypair(1), keypair(1), keypair(2));
+
+        assert_eq!((&sk1.p, &sk1.q, &sk1.d), (&sk2.p, &sk2.q, &sk2.d));
+        assert_ne!(sk1.n, sk3.n);
+    }
+
+    #[test]
+    fn rejecting_invalid_keypair_parameters() {
+        let e = BigUint::from(DEFAULT_EXPONENT);
+        assert!(gen_keypair_with_exponent(MIN_KEYSIZE - 1, &e).is_err());
+        for &bad_e in &[0u32, 1, 2, 65_536] {
+            assert_eq!(
+                gen_keypair_with_exponent(64, &BigUint::from(bad_e)).err(),
+                Some(Error::InvalidExponent)
+            );
+        }
+        // too large, for the modulus or at all
+        let too_large = [
+            (64, (BigUint::one() << 64) + 1u32),
+            (1024, (BigUint::one() << 256) + 1u32),
+        ];
+        for (keysize, bad_e) in &too_large {
+            assert_eq!(
+                gen_keypair_with_exponent(*keysize, bad_e).err(),
+                Some(Error::InvalidExponent)
+            );
+        }
+    }
+
+    #[test]
+    fn encrypting_and_decrypting() {
+        let (pk, sk) = gen_keypair(1024);
+
+        let m = b"hello world i am here!";
+        let c = encrypt(m, &pk).unwrap();
+        let m_ = &decrypt(&c, &sk).unwrap()[..];
+
+        assert_eq!(m, m_);
+    }
+
+    #[test]
+    fn encrypting_and_decrypting_with_custom_oaep_params() {
+        let (pk, sk) = gen_keypair(1024);
+        let params = OaepParams::new(HashFunction::Sha256).with_label(b"label");
+
+        let c = encrypt_with(b"hello world i am here!", &pk, &params).unwrap();
+        assert_eq!(
+            decrypt_with(&c, &sk, &params).unwrap(),
+            b"hello world i am here!"
+        );
+        assert_eq!(decrypt(&c, &sk), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn encrypting_the_same_message_twice_yields_different_cyphertexts() {
+        let (pk, _) = gen_keypair(1024);
+        assert_ne!(encrypt(b"hi", &pk).unwrap(), encrypt(b"hi", &pk).unwrap());
+    }
+
+    #[test]
+    fn rejecting_messages_too_long_for_modulus() {
+        let (pk, _) = gen_keypair(512);
+        // with SHA-1, at most k - 2 * 20 - 2 bytes fit
+        let max_len = byte_len(&pk.n) - 42;
+        assert!(encrypt(&vec![0xff; max_len], &pk).is_ok());
+        assert_eq!(
+            encrypt(&vec![0xff; max_len + 1], &pk),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn encrypting_and_decrypting_long_messages_with_hybrid_encryption() {
+        let (pk, sk) = gen_keypair(1024);
+        let m = vec![0xab; 10_000];
+
+        let c = encrypt_hybrid(&m, &pk).unwrap();
+        assert_eq!(c.payload.len(), m.len());
+        assert_eq!(decrypt_hybrid(&c, &sk).unwrap(), m);
+        assert_eq!(
+            decrypt_hybrid(&encrypt_hybrid(b"", &pk).unwrap(), &sk).unwrap(),
+            b""
+        );
+    }
+
+    #[test]
+    fn failing_to_decrypt_hybrid_cyphertexts_with_wrong_key() {
+        let (pk, _) = gen_keypair(1024);
+        let (_, wrong_sk) = gen_keypair(1024);
+        let c = encrypt_hybrid(b"hello world i am here!", &pk).unwrap();
+        assert!(decrypt_hybrid(&c, &wrong_sk).is_err());
+    }
+
+    #[test]
+    fn rejecting_moduli_too_short_to_wrap_a_key() {
+        let (pk, _) = gen_keypair(512);
+        assert_eq!(
+            encrypt_hybrid(b"hello", &pk).err(),
+            Some(Error::ModulusTooShort)
+        );
+    }
+
+    #[test]
+    fn encrypting_rsa_labs_oaep_test_vectors() {
+        // examples 1.2 and 1.3 of `oaep-vect.txt`, the RSA Laboratories test vectors for
+        // PKCS #1 v2.1 (SHA-1 for the label and MGF1, and an empty label): a fixed seed
+        // makes the encryption deterministic, so the cyphertext must match exactly
+        let pk = PublicKey {
+            n: hex_to_int(
+                "a8b3b284af8eb50b387034a860f146c4919f318763cd6c5598c8ae4811a1e0abc4c7e0b082d693a5e7fced\
+                 675cf4668512772c0cbc64a742c6c630f533c8cc72f62ae833c40bf25842e984bb78bdbf97c0107d55bdb6\
+                 62f5c4e0fab9845cb5148ef7392dd3aaff93ae1e6b667bb3d4247616d4f5ba10d4cfd226de88d39f16fb",
+            ),
+            e: BigUint::from(65_537u32),
+        };
+        let examples = [
+            (
+                "750c4047f547e8e41411856523298ac9bae245efaf1397fbe56f9dd5",
+                "0cc742ce4a9b7f32f951bcb251efd925fe4fe35f",
+                "640db1acc58e0568fe5407e5f9b701dff8c3c91e716c536fc7fcec6cb5b71c1165988d4a279e1577d730fc\
+                 7a29932e3f00c81515236d8d8e31017a7a09df4352d904cdeb79aa583adcc31ea698a4c05283daba9089be\
+                 5491f67c1a4ee48dc74bbbe6643aef846679b4cb395a352d5ed115912df696ffe0702932946d71492b44",
+            ),
+            (
+                "d94ae0832e6445ce42331cb06d531a82b1db4baad30f746dc916df24d4e3c2451fff59a6423eb0e1d02d4f\
+                 e646cf699dfd818c6e97b051",
+                "2514df4695755a67b288eaf4905c36eec66fd2fd",
+                "423736ed035f6026af276c35c0b3741b365e5f76ca091b4e8c29e2f0befee603595aa8322d602d2e625e95\
+                 eb81b2f1c9724e822eca76db8618cf09c5343503a4360835b5903bc637e3879fb05e0ef32685d5aec5067c\
+                 d7cc96fe4b2670b6eac3066b1fcf5686b68589aafb7d629b02d8f8625ca3833624d4800fb081b1cf94eb",
+            ),
+        ];
+        for (m, seed, c) in examples.iter() {
+            let (m, seed) = (hex::decode(m).unwrap(), hex::decode(seed).unwrap());
+            let em = oaep::encode(&m, byte_len(&pk.n), &OaepParams::default(), &seed).unwrap();
+            assert_eq!(encode_os2ip(&em).modpow(&pk.e, &pk.n), hex_to_int(c));
+        }
+    }
+
+    #[test]
+    fn decrypting_cyphertexts_produced_by_openssl() {
+        let sk = openssl_fixture_secret_key();
+        let c_sha1 = hex_to_int(
+            "0beee9cc4a3856512e3eb799f6aa783277f39b2ea03f812497eaddd08186fea7d435b2a86a4dbf5427953e\
+             390a23a6148c22a28c827d7c97404ad5848501f4fe900f0ea7285581a259eefcc91c0ecc81358c6cb7688f\
+             92b4e156f18e004fbdab9944f08a176799e82d4c622a5536e4929cf829bb6d30b7cb81677c2139e0aa60",
+        );
+        let c_sha256_labelled = hex_to_int(
+            "8f293fcbe291a57176c5579d3a7594eb620ffb04f8c04e79c718c97f2179de16951559fcd938831d16deb8\
+             957e87e2b216b52c860408841e08c68dc5e8bdcb4193e8d6195b0305852b80c0606cfd81bc20b67145c22e\
+             b2d85643e9d29b152902af160ff6747c639a9a4bfc7e1396f0594bf72a02dc230a0beed403a9d4a98edb",
+        );
+        let sha256_labelled = OaepParams::new(HashFunction::Sha256).with_label(b"label");
+
+        assert_eq!(decrypt(&c_sha1, &sk).unwrap(), b"cryptopals");
+        assert_eq!(
+            decrypt_with(&c_sha256_labelled, &sk, &sha256_labelled).unwrap(),
+            b"cryptopals"
+        );
+    }
+
+    #[test]
+    fn encrypting_and_decrypting_with_pkcs1v15_padding() {
+        let (pk, sk) = gen_keypair(1024);
+        let c = encrypt_pkcs1v15(b"hello world i am here!", &pk).unwrap();
+        assert_eq!(
+            decrypt_pkcs1v15(&c, &sk).unwrap(),
+            b"hello world i am here!"
+        );
+        assert_eq!(decrypt(&c, &sk), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn decrypting_pkcs1v15_cyphertext_produced_by_openssl() {
+        let sk = openssl_fixture_secret_key();
+        let c = hex_to_int(
+            "1fb9a95ad0516052130a9aaaf087efbea58db1579f7a2efcb838f8f2ee4b62c0e01ba105dccc8b98b122d1\
+             29112c8b0973767ecc54261fe47709ebe5889b118e0212feff1e34eb017b9b72c41400764aa1ec75281b0d\
+             09eb91b8a886261ab579645feb14e30fceeda2629da32525a3421fcd735a8aa88d8aa4fb1d1c29c6d50c",
+        );
+        assert_eq!(decrypt_pkcs1v15(&c, &sk).unwrap(), b"cryptopals");
+    }
+
+    #[test]
+    fn signing_and_verifying_with_pkcs1v15() {
+        let (pk, sk) = gen_keypair(1024);
+        let s = sign_pkcs1v15(b"hello world", &sk, HashFunction::Sha256).unwrap();
+
+        assert_eq!(
+            verify_pkcs1v15(b"hello world", &s, &pk, HashFunction::Sha256),
+            Ok(())
+        );
+        assert_eq!(
+            verify_pkcs1v15(b"hello world!", &s, &pk, HashFunction::Sha256),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            verify_pkcs1v15(b"hello world", &s, &pk, HashFunction::Sha1),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn signing_deterministically_with_pkcs1v15_as_openssl_does() {
+        let sk = openssl_fixture_secret_key();
+        let expected_sha1 = hex_to_int(
+            "4dd1405685a516dd7dc631de4dfd16fb047df8fa3e7e2af59dc3b3fe0acf705981da98950999ac2c1765d6\
+             d07647dde0e7ed7f33c30f62d6de995520d1419cf06e93991e206e11302e69f71caee1b2c2ced55386e96d\
+             439a1d909eedc8c98405fd561d9a8d9b56d2a17b2dcd74d952bc6c64eee7eb61bb1b3d16583f474b7134",
+        );
+        let expected_sha256 = hex_to_int(
+            "584cac2d33094a11c301eb403538826f3d57ccd4e18a967136e5194a79f92c22de59a12fd8e79ccd55e82a\
+             9a45d526c6e11596dfa537bc5c9e460a75cde42b818aef24145d0dd11c7682f5d50562cb26fb2c79283d06\
+             67b02e48b7fa0bf498b5434a7589aefb64249effba10018b18056b33b44a09f0934b7bee943b7959d2b9",
+        );
+
+        assert_eq!(
+            sign_pkcs1v15(b"cryptopals", &sk, HashFunction::Sha1).unwrap(),
+            expected_sha1
+        );
+        assert_eq!(
+            sign_pkcs1v15(b"cryptopals", &sk, HashFunction::Sha256).unwrap(),
+            expected_sha256
+        );
+        assert_eq!(
+            verify_pkcs1v15(
+                b"cryptopals",
+                &expected_sha256,
+                &openssl_fixture_public_key(),
+                HashFunction::Sha256
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn signing_and_verifying_with_pss() {
+        let (pk, sk) = gen_keypair(1024);
+        let params = PssParams::new(HashFunction::Sha256);
+        let s = sign_pss(b"hello world", &sk, &params).unwrap();
+
+        assert_eq!(verify_pss(b"hello world", &s, &pk, &params), Ok(()));
+        assert_eq!(
+            verify_pss(b"hello world!", &s, &pk, &params),
+            Err(Error::InvalidSignature)
+        );
+        assert_ne!(s, sign_pss(b"hello world", &sk, &params).unwrap());
+    }
+
+    #[test]
+    fn verifying_pss_signature_produced_by_openssl() {
+        let pk = openssl_fixture_public_key();
+        let s = hex_to_int(
+            "008ee11bf154634567ade60699bfc135ad2aed963c0752258283c18bd68292c2758d13c0cedf8452d0f97c\
+             517eba9d7f99a9500bd695600cf1afb77b51e21ca60ad937ab4c728f63196d9f7afac4fba8f6bfefc305c1\
+             11206aa4811bf07d5694e9990b9e2866dc760dc8758e407c5ef13f0d90812513d4d5e8de667a5fc1084f",
+        );
+        let params = PssParams::new(HashFunction::Sha256);
+
+        assert_eq!(verify_pss(b"cryptopals", &s, &pk, &params), Ok(()));
+        assert_eq!(
+            verify_pss(b"cryptopals", &(&s + 1u32), &pk, &params),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn building_secret_key_from_primes() {
+        let sk = openssl_fixture_secret_key();
+        let p_1 = &sk.p - 1u32;
+        let q_1 = &sk.q - 1u32;
+
+        assert_eq!(sk.n, &sk.p * &sk.q);
+        assert_eq!((&sk.e * &sk.d) % &sk.lambda_n, BigUint::one());
+        assert_eq!((&sk.e * &sk.d_p) % &p_1, BigUint::one());
+        assert_eq!((&sk.e * &sk.d_q) % &q_1, BigUint::one());
+        assert_eq!((&sk.q * &sk.q_inv) % &sk.p, BigUint::one());
+    }
+
+    #[test]
+    fn refusing_to_build_secret_key_from_unsuitable_primes() {
+        let p = BigUint::from(61u32);
+        let q = BigUint::from(53u32);
+        // lambda(61 * 53) = lcm(60, 52) = 780 = 2^2 * 3 * 5 * 13
+        assert!(SecretKey::from_primes(p.clone(), q.clone(), BigUint::from(17u32)).is_some());
+        assert!(SecretKey::from_primes(p.clone(), q, BigUint::from(13u32)).is_none());
+        assert!(SecretKey::from_primes(p.clone(), p, BigUint::from(17u32)).is_none());
+    }
+
+    #[test]
+    fn decrypting_with_crt_matches_decrypting_with_d() {
+        let (pk, sk) = gen_keypair(512);
+        let c = BigUint::from(123_456_789u32).modpow(&pk.e, &pk.n);
+        assert_eq!(decrypt_crt(&c, &sk).unwrap(), c.modpow(&sk.d, &sk.n));
+        assert_eq!(decrypt_crt(&c, &sk).unwrap(), BigUint::from(123_456_789u32));
+    }
+
+    #[test]
+    fn decrypting_with_crt_after_swapping_primes() {
+        let (pk, sk) = gen_keypair(512);
+        let c = BigUint::from(123_456_789u32).modpow(&pk.e, &pk.n);
+        let sk = SecretKey {
+            q_inv: mod_inverse(&sk.p, &sk.q).unwrap(),
+            p: sk.q,
+            q: sk.p,
+            d_p: sk.d_q,
+            d_q: sk.d_p,
+            ..sk
+        };
+        assert_eq!(decrypt_crt(&c, &sk).unwrap(), BigUint::from(123_456_789u32));
+    }
+
+    #[test]
+    fn generating_blinding_factors() {
+        let (_, sk) = gen_keypair(512);
+        let mut rng = OsRng::new().unwrap();
+        let (r1, r1_inv) = gen_blinding_factor(&mut rng, &sk.n);
+        let (r2, _) = gen_blinding_factor(&mut rng, &sk.n);
+
+        assert_eq!((&r1 * &r1_inv) % &sk.n, BigUint::one());
+        assert_ne!(r1, r2);
+    }
+
+    #[test]
+    fn withholding_faulty_crt_results() {
+        let (pk, mut sk) = gen_keypair(512);
+        let c = BigUint::from(123_456_789u32).modpow(&pk.e, &pk.n);
+        sk.d_p += 1u32;
+        assert_eq!(decrypt_crt(&c, &sk), Err(Error::Fault));
+    }
+
+    #[test]
+    fn importing_keys_exported_by_openssl() {
+        let fixture = openssl_fixture_secret_key();
+        let read = |path| std::fs::read_to_string(path).unwrap();
+
+        for sk in [
+            SecretKey::from_pem(&read("data/rsa_private_pkcs1.pem")).unwrap(),
+            SecretKey::from_pem(&read("data/rsa_private_pkcs8.pem")).unwrap(),
+            SecretKey::from_pkcs8_der(&std::fs::read("data/rsa_private_pkcs8.der").unwrap())
+                .unwrap(),
+        ] {
+            assert_eq!(
+                (&sk.n, &sk.e, &sk.p, &sk.q),
+                (&fixture.n, &fixture.e, &fixture.p, &fixture.q)
+            );
+            assert_eq!(
+                (&sk.d_p, &sk.d_q, &sk.q_inv),
+                (&fixture.d_p, &fixture.d_q, &fixture.q_inv)
+            );
+        }
+        for pk in [
+            PublicKey::from_pem(&read("data/rsa_public_pkcs1.pem")).unwrap(),
+            PublicKey::from_pem(&read("data/rsa_public_spki.pem")).unwrap(),
+            PublicKey::from_spki_der(&std::fs::read("data/rsa_public_spki.der").unwrap()).unwrap(),
+        ] {
+            assert_eq!((&pk.n, &pk.e), (&fixture.n, &fixture.e));
+        }
+    }
+
+    #[test]
+    fn exporting_keys_as_openssl_does() {
+        let read = |path| std::fs::read_to_string(path).unwrap();
+        let sk = SecretKey::from_pem(&read("data/rsa_private_pkcs8.pem")).unwrap();
+        let pk = sk.public_key();
+
+        assert_eq!(sk.to_pkcs1_pem(), read("data/rsa_private_pkcs1.pem"));
+        assert_eq!(sk.to_pkcs8_pem(), read("data/rsa_private_pkcs8.pem"));
+        assert_eq!(
+            sk.to_pkcs8_der(),
+            std::fs::read("data/rsa_private_pkcs8.der").unwrap()
+        );
+        assert_eq!(pk.to_pkcs1_pem(), read("data/rsa_public_pkcs1.pem"));
+        assert_eq!(pk.to_spki_pem(), read("data/rsa_public_spki.pem"));
+        assert_eq!(
+            pk.to_spki_der(),
+            std::fs::read("data/rsa_public_spki.der").unwrap()
+        );
+    }
+
+    #[test]
+    fn exporting_and_importing_generated_keys() {
+        let (pk, sk) = gen_keypair(512);
+        let sk_ = SecretKey::from_pem(&sk.to_pkcs1_pem()).unwrap();
+        let pk_ = PublicKey::from_pem(&pk.to_spki_pem()).unwrap();
+
+        assert_eq!(sk_.to_pkcs8_der(), sk.to_pkcs8_der());
+        assert_eq!(pk_.to_pkcs1_der(), pk.to_pkcs1_der());
+        assert_eq!(sk_.lambda_n, sk.lambda_n);
+    }
+
+    #[test]
+    fn rejecting_keys_of_the_wrong_kind_or_inconsistent_keys() {
+        let sk = openssl_fixture_secret_key();
+        let mut inconsistent = openssl_fixture_secret_key();
+        inconsistent.n += 2u32;
+        let corrupted = |corrupt: fn(&mut SecretKey)| {
+            let mut sk = openssl_fixture_secret_key();
+            corrupt(&mut sk);
+            SecretKey::from_pkcs1_der(&sk.to_pkcs1_der()).err()
+        };
+
+        assert_eq!(
+            PublicKey::from_pem(&sk.to_pkcs8_pem()).err(),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            SecretKey::from_pem(&sk.public_key().to_spki_pem()).err(),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            SecretKey::from_pkcs1_der(&inconsistent.to_pkcs1_der()).err(),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            corrupted(|sk| sk.d += 2u32),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            corrupted(|sk| sk.d_p += 1u32),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            corrupted(|sk| sk.d_q -= 1u32),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(
+            corrupted(|sk| sk.q_inv += 1u32),
+            Some(Error::InvalidKeyEncoding)
+        );
+        assert_eq!(corrupted(|_| ()), None);
+    }
+
+    #[test]
+    fn encrypting_and_decrypting_unpadded() {
+        let (pk, sk) = gen_keypair(512);
+        let m = BigUint::from(42u32);
+        let c = encrypt_unpadded(&m, &pk).unwrap();
+
+        assert_eq!(c, m.modpow(&pk.e, &pk.n));
+        assert_eq!(decrypt_unpadded(&c, &sk).unwrap(), m);
+        assert_eq!(
+            encrypt_unpadded(&pk.n, &pk).err(),
+            Some(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn rejecting_cyphertexts_not_smaller_than_modulus() {
+        let sk = openssl_fixture_secret_key();
+        assert_eq!(decrypt(&sk.n, &sk), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn generating_distinct_primes() {
+        let e = BigUint::from(3u32);
+        let mut rng = OsRng::new().unwrap();
+        let (p1, q1) = gen_distinct_primes(&mut rng, 64, &e).unwrap();
+        let (p2, q2) = gen_distinct_primes(&mut rng, 64, &e).unwrap();
+
+        [&p1, &q1, &p2, &q2]
+            .iter()
+            .for_each(|n| assert!(primality::is_probable_prime(n)));
+        assert_ne!(p1, p2);
+        assert_ne!(q1, q2);
+        assert_ne!(p1, q1);
+        assert_ne!(p2, q2);
+    }
+
+    #[test]
+    fn generating_primes_with_top_bits_set() {
+        let mut rng = OsRng::new().unwrap();
+        let e = BigUint::from(3u32);
+        for &bits in &[8, 33, 128] {
+            let p = gen_prime(&mut rng, bits, &e).unwrap();
+            assert_eq!(p.bits(), bits);
+            assert_eq!(&p >> (bits - 2), BigUint::from(3u32));
+            assert!(gcd(&p - 1u32, e.clone()).is_one());
+        }
+
+        // every 8-bit prime with its top two bits set (193 to 251) is 1 mod 3, 5, 7, 29 or 113
+        let e = BigUint::from(3u32 * 5 * 7 * 29 * 113);
+        assert_eq!(gen_prime(&mut rng, 8, &e), None);
+        assert_eq!(
+            gen_distinct_primes(&mut rng, 16, &e),
+            Err(Error::KeyGeneration)
+        );
+    }
+
+    #[test]
+    fn encoding_and_decoding() {
+        let m = b"hello world i am here";
+        let params = OaepParams::default();
+        let encoded = encode(m, 128, &params).unwrap();
+        assert_eq!(m, &decode(&encoded, 128, &params).unwrap()[..]);
+    }
+
+    #[test]
+    fn encoding_byte_array_as_int() {
+        assert_eq!(encode_os2ip(&[0xFF, 0xFF]), BigUint::new(vec![65535]));
+        assert_eq!(encode_os2ip(&[0x01, 0x00]), BigUint::new(vec![256]));
+
+        assert_eq!(encode_os2ip(b"xyz"), BigUint::new(vec![7895418]));
+        assert_eq!(encode_os2ip(&[120, 121, 122]), BigUint::new(vec![7895418]));
+    }
+
+    #[test]
+    fn decoding_int_as_bytearray() {
+        assert_eq!(
+            decode_i2osp(&BigUint::new(vec![65535]), 2),
+            vec![0xFF, 0xFF]
+        );
+        assert_eq!(decode_i2osp(&BigUint::new(vec![256]), 2), vec![0x01, 0x00]);
+
+        assert_eq!(decode_i2osp(&BigUint::new(vec![7895418]), 3), b"xyz");
+        assert_eq!(
+            decode_i2osp(&BigUint::new(vec![7895418]), 3),
+            &[120, 121, 122]
+        );
+    }
+
+    #[test]
+    fn checking_for_zero_bytes() {
+        assert_eq!(ct_is_zero(0x00), 1);
+        assert_eq!(ct_is_zero(0x01), 0);
+        assert_eq!(ct_is_zero(0xff), 0);
+    }
+
+    #[test]
+    fn decoding_int_as_zero_padded_bytearray() {
+        assert_eq!(
+            decode_i2osp(&BigUint::new(vec![256]), 4),
+            vec![0x00, 0x00, 0x01, 0x00]
+        );
+        assert_eq!(decode_i2osp(&BigUint::zero(), 2), vec![0x00, 0x00]);
+    }
+
+    /// a 1024-bit key generated with `openssl genpkey -algorithm RSA`
+    fn openssl_fixture_secret_key() -> SecretKey {
+        let p = hex_to_int(
+            "ce4a99e8b8005c1f2e1820e4ee97d6b6c33cd7934019958a1fc4e3a49e42acfe6938dd8b79d591bb\
+             6680af9d85fa0370d7fec712b33a6d9e1673c69812ff7b87",
+        );
+        let q = hex_to_int(
+            "c1714af69b1b3d84d2d707d99b7523f41c644b971d6f258f636102ed274a6b6280deec6ff92c5bfa\
+             d1c2f575481eaf1c2779353baae71065e30d9e7cab95a499",
+        );
+        SecretKey::from_primes(p, q, BigUint::from(65_537u32)).unwrap()
+    }
+
+    fn openssl_fixture_public_key() -> PublicKey {
+        PublicKey {
+            n: openssl_fixture_secret_key().n,
+            e: BigUint::from(65_537u32),
+        }
+    }
+
+    fn hex_to_int(hex: &str) -> BigUint {
+        BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+    }
+}
@@ -0,0 +1,183 @@
+use super::{ct_is_zero, Error};
+use crate::hashing::{self, HashFunction};
+use crate::xor_cypher;
+
+/// Parameters for EME-OAEP encoding, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.1
+///
+/// The `Default` parameters are the ones RFC 8017 names as defaults
+/// (SHA-1 for both hashing the label and MGF1, and an empty label).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OaepParams {
+    pub hash: HashFunction,
+    pub mgf_hash: HashFunction,
+    pub label: Vec<u8>,
+}
+
+impl OaepParams {
+    /// uses `hash` for both the label and MGF1, with an empty label
+    pub fn new(hash: HashFunction) -> OaepParams {
+        Self {
+            hash,
+            mgf_hash: hash,
+            label: Vec::new(),
+        }
+    }
+
+    pub fn with_label(self, label: &[u8]) -> OaepParams {
+        Self {
+            label: label.to_vec(),
+            ..self
+        }
+    }
+
+    /// the longest message (in bytes) that can be encoded for a modulus of `k` bytes
+    pub fn max_message_len(&self, k: usize) -> usize {
+        k.saturating_sub(2 * self.hash.output_len() + 2)
+    }
+}
+
+impl Default for OaepParams {
+    fn default() -> OaepParams {
+        OaepParams::new(HashFunction::Sha1)
+    }
+}
+
+/// Encodes message `m` into an encoded message of `k` bytes (the length of the modulus):
+///
+/// EM = 0x00 || maskedSeed || maskedDB, where DB = lHash || PS || 0x01 || M
+///
+/// `seed` must be `params.hash.output_len()` random bytes.
+pub(super) fn encode(
+    m: &[u8],
+    k: usize,
+    params: &OaepParams,
+    seed: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let h_len = params.hash.output_len();
+    if k < 2 * h_len + 2 || m.len() > params.max_message_len(k) {
+        return Err(Error::MessageTooLong);
+    }
+    debug_assert_eq!(seed.len(), h_len);
+
+    let mut db = params.hash.digest(&params.label);
+    db.resize(k - m.len() - h_len - 2, 0);
+    db.push(0x01);
+    db.extend_from_slice(m);
+
+    let masked_db = xor_cypher::xor(&db, hashing::mgf1(params.mgf_hash, seed, db.len()));
+    let masked_seed = xor_cypher::xor(seed, hashing::mgf1(params.mgf_hash, &masked_db, h_len));
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.extend_from_slice(&masked_seed);
+    em.extend_from_slice(&masked_db);
+    Ok(em)
+}
+
+/// Recovers the message from an encoded message `em` (as long as the modulus).
+///
+/// Every check is performed (and folded into a single flag) regardless of whether
+/// an earlier one failed, and every failure is reported as the same `Error::Decryption`,
+/// so that callers cannot be turned into an oracle distinguishing failure modes
+/// (cf: Manger's attack, https://www.iacr.org/archive/crypto2001/21390229.pdf).
+pub(super) fn decode(em: &[u8], params: &OaepParams) -> Result<Vec<u8>, Error> {
+    let h_len = params.hash.output_len();
+    if em.len() < 2 * h_len + 2 {
+        return Err(Error::Decryption);
+    }
+    let (y, masked_seed, masked_db) = (em[0], &em[1..=h_len], &em[(h_len + 1)..]);
+
+    let seed = xor_cypher::xor(
+        masked_seed,
+        hashing::mgf1(params.mgf_hash, masked_db, h_len),
+    );
+    let db = xor_cypher::xor(
+        masked_db,
+        hashing::mgf1(params.mgf_hash, &seed, masked_db.len()),
+    );
+    let (l_hash, rest) = db.split_at(h_len);
+
+    let mut bad = y;
+    bad |= params
+        .hash
+        .digest(&params.label)
+        .iter()
+        .zip(l_hash.iter())
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+    // scan PS || 0x01 for the index of the separator without branching on secret bytes
+    let mut separator_idx = 0;
+    let mut found = 0u8;
+    let mut invalid_ps = 0u8;
+    for (idx, &b) in rest.iter().enumerate() {
+        let (is_zero, is_one) = (ct_is_zero(b), ct_is_zero(b ^ 0x01));
+        let not_found = found ^ 1;
+        separator_idx |= ((not_found & is_one) as usize).wrapping_neg() & idx;
+        invalid_ps |= not_found & ((is_zero | is_one) ^ 1);
+        found |= is_one;
+    }
+    bad |= invalid_ps | (found ^ 1);
+
+    if bad != 0 {
+        return Err(Error::Decryption);
+    }
+    Ok(rest[(separator_idx + 1)..].to_vec())
+}
+
+#[cfg(test)]
+mod oaep_tests {
+    use super::*;
+
+    #[test]
+    fn encoding_and_decoding() {
+        let params = OaepParams::default();
+        let seed = [0xaa; 20];
+        let em = encode(b"hello world i am here", 128, &params, &seed).unwrap();
+        assert_eq!(em.len(), 128);
+        assert_eq!(em[0], 0x00);
+        assert_eq!(decode(&em, &params).unwrap(), b"hello world i am here");
+    }
+
+    #[test]
+    fn encoding_and_decoding_empty_and_maximal_messages() {
+        let params = OaepParams::new(HashFunction::Sha256).with_label(b"label");
+        let seed = [0x55; 32];
+        let longest = vec![0xff; params.max_message_len(128)];
+        for m in [vec![], longest] {
+            let em = encode(&m, 128, &params, &seed).unwrap();
+            assert_eq!(decode(&em, &params).unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn rejecting_messages_too_long_to_encode() {
+        let params = OaepParams::default();
+        let too_long = vec![0xff; params.max_message_len(128) + 1];
+        assert_eq!(
+            encode(&too_long, 128, &params, &[0; 20]),
+            Err(Error::MessageTooLong)
+        );
+        assert_eq!(
+            encode(b"", 41, &params, &[0; 20]),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn failing_to_decode_malformed_messages() {
+        let params = OaepParams::default();
+        let em = encode(b"hello", 128, &params, &[0x01; 20]).unwrap();
+
+        let mut nonzero_first_byte = em.clone();
+        nonzero_first_byte[0] = 0x01;
+        let mut corrupt_db = em.clone();
+        corrupt_db[100] ^= 0x01;
+        let wrong_label = params.clone().with_label(b"wrong");
+
+        assert_eq!(decode(&nonzero_first_byte, &params), Err(Error::Decryption));
+        assert_eq!(decode(&corrupt_db, &params), Err(Error::Decryption));
+        assert_eq!(decode(&em, &wrong_label), Err(Error::Decryption));
+        assert_eq!(decode(&em[..41], &params), Err(Error::Decryption));
+    }
+}
@@ -0,0 +1,223 @@
+use super::{ct_is_zero, Error};
+use crate::hashing::HashFunction;
+use rand::RngCore;
+
+/// minimum number of padding bytes in an EME-PKCS1-v1_5 block
+const MIN_PS_LEN: usize = 8;
+
+/// the longest message (in bytes) that can be encoded for a modulus of `k` bytes
+fn max_message_len(k: usize) -> usize {
+    k.saturating_sub(MIN_PS_LEN + 3)
+}
+
+/// Encodes message `m` into a type 2 block of `k` bytes (the length of the modulus):
+///
+/// EM = 0x00 || 0x02 || PS || 0x00 || M
+///
+/// where PS consists of at least 8 random nonzero bytes, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.2.1
+pub(super) fn encode<R: RngCore>(m: &[u8], k: usize, rng: &mut R) -> Result<Vec<u8>, Error> {
+    if m.len() > max_message_len(k) {
+        return Err(Error::MessageTooLong);
+    }
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.push(0x02);
+    em.extend(gen_nonzero_bytes(rng, k - m.len() - 3));
+    em.push(0x00);
+    em.extend_from_slice(m);
+    Ok(em)
+}
+
+/// Recovers the message from a type 2 block `em` (as long as the modulus), as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-7.2.2
+///
+/// As in `oaep::decode`, every check is performed without branching on the contents
+/// of `em` and every failure yields the same `Error::Decryption`: a decryptor that
+/// reveals *which* check failed (or even just whether the block started with `00 02`)
+/// hands out a Bleichenbacher '98 padding oracle.
+pub(super) fn decode(em: &[u8]) -> Result<Vec<u8>, Error> {
+    if em.len() < MIN_PS_LEN + 3 {
+        return Err(Error::Decryption);
+    }
+    let mut bad = em[0] | (em[1] ^ 0x02);
+
+    // find the first zero byte after the block type without branching on secret bytes
+    let mut separator_idx = 0;
+    let mut found = 0u8;
+    for (idx, &b) in em.iter().enumerate().skip(2) {
+        let is_zero = ct_is_zero(b);
+        separator_idx |= (((found ^ 1) & is_zero) as usize).wrapping_neg() & idx;
+        found |= is_zero;
+    }
+    let ps_too_short = ((separator_idx < MIN_PS_LEN + 2) as u8) & found;
+    bad |= (found ^ 1) | ps_too_short;
+
+    if bad != 0 {
+        return Err(Error::Decryption);
+    }
+    Ok(em[(separator_idx + 1)..].to_vec())
+}
+
+/// DER encoding of the DigestInfo `AlgorithmIdentifier` and `OCTET STRING` header
+/// that precede a digest of `hash` in a signature, as listed in:
+/// https://tools.ietf.org/html/rfc8017#section-9.2 (note 1)
+pub(crate) fn digest_info_prefix(hash: HashFunction) -> &'static [u8] {
+    match hash {
+        HashFunction::Sha1 => &[
+            0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04,
+            0x14,
+        ],
+        HashFunction::Sha224 => &[
+            0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x04, 0x05, 0x00, 0x04, 0x1c,
+        ],
+        HashFunction::Sha256 => &[
+            0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x01, 0x05, 0x00, 0x04, 0x20,
+        ],
+        HashFunction::Sha384 => &[
+            0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x02, 0x05, 0x00, 0x04, 0x30,
+        ],
+        HashFunction::Sha512 => &[
+            0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x03, 0x05, 0x00, 0x04, 0x40,
+        ],
+    }
+}
+
+/// Encodes (a digest of) message `m` into a type 1 block of `k` bytes:
+///
+/// EM = 0x00 || 0x01 || PS || 0x00 || DigestInfo
+///
+/// where PS consists of at least 8 `0xff` bytes, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-9.2
+pub(super) fn encode_signature(m: &[u8], k: usize, hash: HashFunction) -> Result<Vec<u8>, Error> {
+    let prefix = digest_info_prefix(hash);
+    let t_len = prefix.len() + hash.output_len();
+    if k < t_len + MIN_PS_LEN + 3 {
+        return Err(Error::ModulusTooShort);
+    }
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.push(0x01);
+    em.resize(k - t_len - 1, 0xff);
+    em.push(0x00);
+    em.extend_from_slice(prefix);
+    em.extend(hash.digest(m));
+    Ok(em)
+}
+
+fn gen_nonzero_bytes<R: RngCore>(rng: &mut R, len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len);
+    let mut buf = [0u8; 1];
+    while bytes.len() < len {
+        rng.fill_bytes(&mut buf);
+        if buf[0] != 0 {
+            bytes.push(buf[0]);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod pkcs1v15_tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn encoding_and_decoding() {
+        let mut rng = OsRng::new().unwrap();
+        let em = encode(b"hello world i am here", 128, &mut rng).unwrap();
+
+        assert_eq!(em.len(), 128);
+        assert_eq!(&em[..2], &[0x00, 0x02]);
+        assert!(em[2..(128 - 22)].iter().all(|&b| b != 0));
+        assert_eq!(em[128 - 22], 0x00);
+        assert_eq!(decode(&em).unwrap(), b"hello world i am here");
+    }
+
+    #[test]
+    fn encoding_and_decoding_empty_and_maximal_messages() {
+        let mut rng = OsRng::new().unwrap();
+        for m in [vec![], vec![0xff; max_message_len(64)]] {
+            let em = encode(&m, 64, &mut rng).unwrap();
+            assert_eq!(decode(&em).unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn rejecting_messages_too_long_to_encode() {
+        let mut rng = OsRng::new().unwrap();
+        assert_eq!(
+            encode(&[0xff; 54], 64, &mut rng),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn failing_to_decode_malformed_blocks() {
+        let valid = {
+            let mut em = vec![0x00, 0x02];
+            em.extend(vec![0xff; 8]);
+            em.push(0x00);
+            em.extend(b"hello");
+            em
+        };
+        assert_eq!(decode(&valid).unwrap(), b"hello");
+
+        let mut nonzero_first_byte = valid.clone();
+        nonzero_first_byte[0] = 0x01;
+        let mut wrong_block_type = valid.clone();
+        wrong_block_type[1] = 0x01;
+        let mut short_padding = valid.clone();
+        short_padding[9] = 0x00;
+        let no_separator = {
+            let mut em = valid.clone();
+            em[10] = 0xff;
+            em.iter_mut().skip(11).for_each(|b| *b = 0xff);
+            em
+        };
+
+        assert_eq!(decode(&nonzero_first_byte), Err(Error::Decryption));
+        assert_eq!(decode(&wrong_block_type), Err(Error::Decryption));
+        assert_eq!(decode(&short_padding), Err(Error::Decryption));
+        assert_eq!(decode(&no_separator), Err(Error::Decryption));
+        assert_eq!(decode(&valid[..10]), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn encoding_a_signature_block() {
+        let em = encode_signature(b"hello", 64, HashFunction::Sha1).unwrap();
+        let digest_info = [
+            digest_info_prefix(HashFunction::Sha1),
+            &HashFunction::Sha1.digest(b"hello")[..],
+        ]
+        .concat();
+
+        assert_eq!(em.len(), 64);
+        assert_eq!(&em[..2], &[0x00, 0x01]);
+        assert!(em[2..(64 - 36)].iter().all(|&b| b == 0xff));
+        assert_eq!(em[64 - 36], 0x00);
+        assert_eq!(&em[(64 - 35)..], &digest_info[..]);
+    }
+
+    #[test]
+    fn rejecting_moduli_too_short_for_digest_info() {
+        // sha-256 DigestInfo is 51 bytes, so 51 + 8 + 3 = 62 bytes are needed
+        assert!(encode_signature(b"hello", 62, HashFunction::Sha256).is_ok());
+        assert_eq!(
+            encode_signature(b"hello", 61, HashFunction::Sha256),
+            Err(Error::ModulusTooShort)
+        );
+    }
+
+    #[test]
+    fn decoding_an_empty_message() {
+        let mut em = vec![0x00, 0x02];
+        em.extend(vec![0xff; 8]);
+        em.push(0x00);
+        assert_eq!(decode(&em).unwrap(), b"");
+    }
+}
@@ -0,0 +1,177 @@
+use super::Error;
+use crate::hashing::{self, HashFunction};
+use crate::xor_cypher;
+
+/// Parameters for EMSA-PSS encoding, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-9.1
+///
+/// The `Default` parameters are the ones RFC 8017 names as defaults
+/// (SHA-1 for both hashing the message and MGF1, and a 20-byte salt).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PssParams {
+    pub hash: HashFunction,
+    pub mgf_hash: HashFunction,
+    pub salt_len: usize,
+}
+
+impl PssParams {
+    /// uses `hash` for both the message and MGF1, with a salt as long as a digest
+    pub fn new(hash: HashFunction) -> PssParams {
+        Self {
+            hash,
+            mgf_hash: hash,
+            salt_len: hash.output_len(),
+        }
+    }
+
+    pub fn with_salt_len(self, salt_len: usize) -> PssParams {
+        Self { salt_len, ..self }
+    }
+}
+
+impl Default for PssParams {
+    fn default() -> PssParams {
+        PssParams::new(HashFunction::Sha1)
+    }
+}
+
+/// Encodes (a digest of) message `m` into an encoded message of `em_bits` bits:
+///
+/// EM = maskedDB || H || 0xbc, where H = Hash(0x00 * 8 || mHash || salt)
+/// and DB = PS || 0x01 || salt
+///
+/// `salt` must be `params.salt_len` random bytes.
+pub(super) fn encode(
+    m: &[u8],
+    em_bits: usize,
+    params: &PssParams,
+    salt: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let (h_len, s_len) = (params.hash.output_len(), params.salt_len);
+    let em_len = em_bits.div_ceil(8);
+    if em_len < h_len + s_len + 2 {
+        return Err(Error::ModulusTooShort);
+    }
+    debug_assert_eq!(salt.len(), s_len);
+
+    let h = digest_with_salt(m, salt, params.hash);
+    let mut db = vec![0; em_len - s_len - h_len - 2];
+    db.push(0x01);
+    db.extend_from_slice(salt);
+
+    let mut masked_db =
+        xor_cypher::xor(&db, hashing::mgf1(params.mgf_hash, &h, db.len())).into_vec();
+    masked_db[0] &= leftmost_byte_mask(em_len, em_bits);
+
+    let mut em = masked_db;
+    em.extend(h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// Checks that `em` (an encoded message of `em_bits` bits) is a valid encoding of `m`, as specified in:
+/// https://tools.ietf.org/html/rfc8017#section-9.1.2
+pub(super) fn verify(m: &[u8], em: &[u8], em_bits: usize, params: &PssParams) -> Result<(), Error> {
+    let (h_len, s_len) = (params.hash.output_len(), params.salt_len);
+    let em_len = em_bits.div_ceil(8);
+    if em.len() != em_len || em_len < h_len + s_len + 2 || em[em_len - 1] != 0xbc {
+        return Err(Error::InvalidSignature);
+    }
+    let (masked_db, h) = (
+        &em[..(em_len - h_len - 1)],
+        &em[(em_len - h_len - 1)..(em_len - 1)],
+    );
+    let mask = leftmost_byte_mask(em_len, em_bits);
+    if masked_db[0] & !mask != 0 {
+        return Err(Error::InvalidSignature);
+    }
+
+    let mut db = xor_cypher::xor(
+        masked_db,
+        hashing::mgf1(params.mgf_hash, h, masked_db.len()),
+    )
+    .into_vec();
+    db[0] &= mask;
+    let ps_len = em_len - h_len - s_len - 2;
+    if db[..ps_len].iter().any(|&b| b != 0) || db[ps_len] != 0x01 {
+        return Err(Error::InvalidSignature);
+    }
+
+    let salt = &db[(db.len() - s_len)..];
+    match digest_with_salt(m, salt, params.hash) == h {
+        true => Ok(()),
+        false => Err(Error::InvalidSignature),
+    }
+}
+
+/// H = Hash(0x00 * 8 || Hash(m) || salt)
+fn digest_with_salt(m: &[u8], salt: &[u8], hash: HashFunction) -> Vec<u8> {
+    let mut m_prime = vec![0; 8];
+    m_prime.extend(hash.digest(m));
+    m_prime.extend_from_slice(salt);
+    hash.digest(&m_prime)
+}
+
+/// mask clearing the `8 * em_len - em_bits` leftmost bits of the first byte of an encoded
+/// message (so that, as an integer, the encoded message is less than the modulus)
+fn leftmost_byte_mask(em_len: usize, em_bits: usize) -> u8 {
+    0xff >> (8 * em_len - em_bits)
+}
+
+#[cfg(test)]
+mod pss_tests {
+    use super::*;
+
+    #[test]
+    fn encoding_and_verifying() {
+        let params = PssParams::default();
+        let em = encode(b"hello", 1023, &params, &[0x42; 20]).unwrap();
+
+        assert_eq!(em.len(), 128);
+        assert_eq!(em[127], 0xbc);
+        assert!(em[0] & 0x80 == 0);
+        assert_eq!(verify(b"hello", &em, 1023, &params), Ok(()));
+        assert_eq!(
+            verify(b"goodbye", &em, 1023, &params),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn encoding_and_verifying_when_em_bits_is_a_multiple_of_8() {
+        let params = PssParams::new(HashFunction::Sha256).with_salt_len(0);
+        let em = encode(b"hello", 1016, &params, &[]).unwrap();
+
+        assert_eq!(em.len(), 127);
+        assert_eq!(verify(b"hello", &em, 1016, &params), Ok(()));
+    }
+
+    #[test]
+    fn rejecting_tampered_encodings() {
+        let params = PssParams::default();
+        let em = encode(b"hello", 1023, &params, &[0x42; 20]).unwrap();
+
+        let mut wrong_trailer = em.clone();
+        wrong_trailer[127] = 0xbd;
+        let mut leftmost_bit_set = em.clone();
+        leftmost_bit_set[0] |= 0x80;
+        let mut tampered_salt = em.clone();
+        tampered_salt[100] ^= 0x01;
+
+        for bad in [wrong_trailer, leftmost_bit_set, tampered_salt] {
+            assert_eq!(
+                verify(b"hello", &bad, 1023, &params),
+                Err(Error::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn rejecting_moduli_too_short_for_salt_and_digest() {
+        let params = PssParams::default();
+        assert_eq!(
+            encode(b"hello", 8 * 41, &params, &[0; 20]),
+            Err(Error::ModulusTooShort)
+        );
+    }
+}
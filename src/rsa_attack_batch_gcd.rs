@@ -0,0 +1,142 @@
+use crate::rsa::{PublicKey, SecretKey};
+use num::integer::gcd;
+use num::traits::One;
+use num_bigint_dig::BigUint;
+
+/*******************************
+ * BATCH GCD ATTACK
+ *******************************/
+
+/// Recovers the secret keys of every public key in `pks` whose modulus shares a prime
+/// with the modulus of another, as found in the wild by (among others):
+/// https://factorable.net/weakkeys12.extended.pdf
+///
+/// Returns the index in `pks` of each broken key along with its secret key. Keys with the
+/// very same modulus share both primes, which no gcd reveals, and are not reported.
+pub fn recover_secret_keys(pks: &[PublicKey]) -> Vec<(usize, SecretKey)> {
+    let moduli = pks.iter().map(|pk| pk.n.clone()).collect::<Vec<_>>();
+    batch_gcd(&moduli)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, g)| !g.is_one())
+        .filter_map(|(i, g)| {
+            let n = &moduli[i];
+            // both primes are shared (with different moduli): find one of them pairwise
+            let p = match &g == n {
+                true => moduli
+                    .iter()
+                    .map(|m| gcd(n.clone(), m.clone()))
+                    .find(|p| !p.is_one() && p != n)?,
+                false => g,
+            };
+            let q = n / &p;
+            SecretKey::from_primes(p, q, pks[i].e.clone()).map(|sk| (i, sk))
+        })
+        .collect()
+}
+
+/// Computes, for each of `moduli`, its gcd with the product of all the others, in
+/// quasi-linear time rather than the quadratic time of computing every pairwise gcd.
+///
+/// Multiplies the moduli up a product tree, then reduces the product P down a remainder
+/// tree modulo the squares of the nodes, so that each leaf n gets P mod n^2. Since n
+/// divides P, (P mod n^2) / n = (P / n) mod n, and gcd((P / n) mod n, n) is the gcd of n
+/// with the product of the other moduli.
+pub fn batch_gcd(moduli: &[BigUint]) -> Vec<BigUint> {
+    let tree = product_tree(moduli);
+    let mut remainders = tree.last().cloned().unwrap_or_default();
+    for level in tree.iter().rev().skip(1) {
+        remainders = level
+            .iter()
+            .enumerate()
+            .map(|(i, node)| &remainders[i / 2] % (node * node))
+            .collect();
+    }
+    remainders
+        .iter()
+        .zip(moduli)
+        .map(|(r, n)| gcd(r / n, n.clone()))
+        .collect()
+}
+
+/// The levels of a binary tree of products, from the leaves (`values`) up to the root
+fn product_tree(values: &[BigUint]) -> Vec<Vec<BigUint>> {
+    let mut tree = vec![];
+    let mut level = values.to_vec();
+    while level.len() > 1 {
+        let products = level.chunks(2).map(|pair| pair.iter().product()).collect();
+        tree.push(std::mem::replace(&mut level, products));
+    }
+    tree.push(level);
+    tree
+}
+
+#[cfg(test)]
+mod rsa_attack_batch_gcd_tests {
+    use super::*;
+    use crate::rsa;
+
+    /// `count` distinct primes (p such that gcd(p - 1, e) = 1)
+    fn gen_primes(count: usize) -> Vec<BigUint> {
+        (0..count)
+            .step_by(2)
+            .flat_map(|_| {
+                let (_, sk) = rsa::gen_keypair(256);
+                vec![sk.p, sk.q]
+            })
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn computing_gcds_with_products_of_others() {
+        let moduli = [15u32, 21, 143, 35, 17]
+            .iter()
+            .map(|&n| BigUint::from(n))
+            .collect::<Vec<_>>();
+        let gcds = [15u32, 21, 1, 35, 1]
+            .iter()
+            .map(|&g| BigUint::from(g))
+            .collect::<Vec<_>>();
+
+        assert_eq!(batch_gcd(&moduli), gcds);
+        assert_eq!(batch_gcd(&[]), vec![]);
+    }
+
+    #[test]
+    fn recovering_secret_keys_sharing_primes() {
+        let ps = gen_primes(9);
+        let pks = [(0, 1), (2, 3), (1, 4), (5, 6), (0, 7), (4, 8)]
+            .iter()
+            .map(|&(i, j)| {
+                let sk = SecretKey::from_primes(
+                    ps[i].clone(),
+                    ps[j].clone(),
+                    rsa::DEFAULT_EXPONENT.into(),
+                );
+                sk.unwrap().public_key()
+            })
+            .collect::<Vec<_>>();
+
+        let recovered = recover_secret_keys(&pks);
+        let broken = recovered.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        assert_eq!(broken, vec![0, 2, 4, 5]);
+        for (i, sk) in recovered {
+            assert_eq!(&sk.p * &sk.q, pks[i].n);
+        }
+    }
+
+    #[test]
+    fn finding_nothing_in_sound_keys() {
+        let ps = gen_primes(6);
+        let pks = ps
+            .chunks(2)
+            .map(|pq| {
+                SecretKey::from_primes(pq[0].clone(), pq[1].clone(), rsa::DEFAULT_EXPONENT.into())
+            })
+            .map(|sk| sk.unwrap().public_key())
+            .collect::<Vec<_>>();
+
+        assert!(recover_secret_keys(&pks).is_empty());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::number_theory;
+use crate::rsa::{self, PublicKey, SecretKey};
+use num::integer::Integer;
+use num::traits::{One, Zero};
+use num_bigint_dig::{BigUint, RandBigInt};
+use rand::rngs::OsRng;
+use std::fmt;
+
+/// Anything that reveals whether RSA cyphertexts decrypt to PKCS#1 v1.5 conforming blocks:
+/// an in-process `PaddingServer`, a wrapper around some padding implementation under test,
+/// or a client for some remote service.
+pub trait PaddingOracle {
+    type Error;
+
+    fn is_conforming(&mut self, c: &BigUint) -> Result<bool, Self::Error>;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error<E> {
+    Oracle(E),
+    /// the oracle's answers are inconsistent with any message (eg: it isn't
+    /// reporting on the blocks starting `00 02`, or on cyphertexts under this key)
+    InconsistentOracle,
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Oracle(err) => write!(f, "oracle error: {}", err),
+            Error::InconsistentOracle => write!(f, "oracle answers are inconsistent"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for Error<E> {}
+
+/// A server that decrypts cyphertexts and reports (only) whether the result
+/// starts with `00 02`, the first check of PKCS#1 v1.5 decoding
+pub struct PaddingServer {
+    sk: SecretKey,
+}
+
+impl PaddingServer {
+    pub fn new(sk: SecretKey) -> PaddingServer {
+        PaddingServer { sk }
+    }
+}
+
+impl PaddingOracle for PaddingServer {
+    type Error = rsa::Error;
+
+    fn is_conforming(&mut self, c: &BigUint) -> Result<bool, rsa::Error> {
+        if c >= &self.sk.n {
+            return Err(rsa::Error::Decryption);
+        }
+        // plain exponentiation rather than `rsa::decrypt_unpadded`: the attack takes tens
+        // of thousands of queries, and blinding can't hide what the answers themselves leak
+        let m = c.modpow(&self.sk.d, &self.sk.n);
+        let em = rsa::decode_i2osp(&m, rsa::byte_len(&self.sk.n));
+        Ok(em[0] == 0x00 && em[1] == 0x02)
+    }
+}
+
+/*******************************
+ * BLEICHENBACHER '98 ATTACK
+ *******************************/
+
+/// Recovers the (still padded) message block of cyphertext `c` (encrypted under `pk`)
+/// from a padding oracle, as described in:
+/// http://archiv.infsec.ethz.ch/education/fs08/secsem/bleichenbacher98.pdf
+/// (cf: cryptopals #47 and #48; step numbers below refer to the paper).
+///
+/// With B = 2^(8 * (k - 2)), a conforming block m satisfies 2B <= m < 3B. Each s for which
+/// c * s^e is conforming narrows the set of intervals m may lie in (step 3), and the
+/// intervals in turn guide the search for the next s (step 2), until a single value is left.
+pub fn recover_message<O: PaddingOracle>(
+    oracle: &mut O,
+    pk: &PublicKey,
+    c: &BigUint,
+) -> Result<BigUint, Error<O::Error>> {
+    let k = rsa::byte_len(&pk.n);
+    let b = BigUint::one() << (8 * (k - 2));
+    let bounds = Bounds {
+        two_b: &b * 2u32,
+        three_b: &b * 3u32,
+    };
+    let mut attack = Attack {
+        oracle,
+        pk,
+        c0: c.clone(),
+    };
+
+    // step 1: blinding (only needed when `c` is not itself conforming)
+    let (s0, s0_inv) = attack.blind()?;
+    attack.c0 = (c * s0.modpow(&pk.e, &pk.n)) % &pk.n;
+
+    // step 2a
+    let mut intervals = vec![(bounds.two_b.clone(), &bounds.three_b - 1u32)];
+    let mut s = attack.search_from(pk.n.div_ceil(&bounds.three_b))?;
+    loop {
+        intervals = narrow(&intervals, &s, &pk.n, &bounds);
+        match intervals.as_slice() {
+            [] => return Err(Error::InconsistentOracle),
+            // step 4
+            [(a, b)] if a == b => return Ok((a * s0_inv) % &pk.n),
+            // step 2c
+            [(a, b)] => s = attack.search_near(a, b, &s, &bounds)?,
+            // step 2b
+            _ => s = attack.search_from(s + 1u32)?,
+        }
+    }
+}
+
+/// 2B and 3B, the (inclusive and exclusive) bounds of conforming blocks
+struct Bounds {
+    two_b: BigUint,
+    three_b: BigUint,
+}
+
+struct Attack<'a, O> {
+    oracle: &'a mut O,
+    pk: &'a PublicKey,
+    c0: BigUint,
+}
+
+impl<'a, O: PaddingOracle> Attack<'a, O> {
+    /// whether c0 * s^e decrypts to a conforming block
+    fn is_conforming(&mut self, s: &BigUint) -> Result<bool, Error<O::Error>> {
+        let c = (&self.c0 * s.modpow(&self.pk.e, &self.pk.n)) % &self.pk.n;
+        self.oracle.is_conforming(&c).map_err(Error::Oracle)
+    }
+
+    /// finds an invertible s0 for which c0 * s0^e is conforming (trying 1 first), and its inverse,
+    /// giving up after as many random tries as there are values of s0
+    fn blind(&mut self) -> Result<(BigUint, BigUint), Error<O::Error>> {
+        if self.is_conforming(&BigUint::one())? {
+            return Ok((BigUint::one(), BigUint::one()));
+        }
+        let mut rng = OsRng::new().expect("Failed to build RNG");
+        let mut tries = BigUint::one();
+        while tries < self.pk.n {
+            let s0 = rng.gen_biguint_below(&self.pk.n);
+            if self.is_conforming(&s0)? {
+                if let Some(s0_inv) = number_theory::mod_inverse(&s0, &self.pk.n) {
+                    return Ok((s0, s0_inv));
+                }
+            }
+            tries += 1u32;
+        }
+        Err(Error::InconsistentOracle)
+    }
+
+    /// steps 2a and 2b: finds the least s >= `start` for which c0 * s^e is conforming
+    /// (which, s being taken mod n, must be less than n)
+    fn search_from(&mut self, start: BigUint) -> Result<BigUint, Error<O::Error>> {
+        let mut s = start;
+        while s < self.pk.n {
+            if self.is_conforming(&s)? {
+                return Ok(s);
+            }
+            s += 1u32;
+        }
+        Err(Error::InconsistentOracle)
+    }
+
+    /// step 2c: with m known to lie in [a, b], searches values of s for which s * m
+    /// would land in [2B, 3B) after wrapping around n r times, for increasing r
+    /// (starting from a value that roughly halves the interval each step), until s reaches n
+    fn search_near(
+        &mut self,
+        a: &BigUint,
+        b: &BigUint,
+        prev_s: &BigUint,
+        bounds: &Bounds,
+    ) -> Result<BigUint, Error<O::Error>> {
+        let n = &self.pk.n;
+        let mut r = ((b * prev_s - &bounds.two_b) * 2u32).div_ceil(n);
+        loop {
+            let r_n = &r * n;
+            let mut s = (&bounds.two_b + &r_n).div_ceil(b);
+            if &s >= n {
+                return Err(Error::InconsistentOracle);
+            }
+            let s_max = (&bounds.three_b + &r_n - 1u32) / a;
+            while s <= s_max && &s < n {
+                if self.is_conforming(&s)? {
+                    return Ok(s);
+                }
+                s += 1u32;
+            }
+            r += 1u32;
+        }
+    }
+}
+
+/// step 3: narrows every interval [a, b] to the values m in it for which
+/// 2B <= m * s - r * n < 3B (for some r), merging any overlapping results
+fn narrow(
+    intervals: &[(BigUint, BigUint)],
+    s: &BigUint,
+    n: &BigUint,
+    bounds: &Bounds,
+) -> Vec<(BigUint, BigUint)> {
+    let mut narrowed = vec![];
+    for (a, b) in intervals {
+        let a_s = a * s;
+        let mut r = match &a_s + 1u32 > bounds.three_b {
+            true => (a_s - &bounds.three_b + 1u32).div_ceil(n),
+            false => BigUint::zero(),
+        };
+        let r_max = (b * s - &bounds.two_b) / n;
+        while r <= r_max {
+            let r_n = &r * n;
+            let lo = (&bounds.two_b + &r_n).div_ceil(s).max(a.clone());
+            let hi = ((&bounds.three_b - 1u32 + &r_n) / s).min(b.clone());
+            if lo <= hi {
+                narrowed.push((lo, hi));
+            }
+            r += 1u32;
+        }
+    }
+    merge(narrowed)
+}
+
+fn merge(mut intervals: Vec<(BigUint, BigUint)>) -> Vec<(BigUint, BigUint)> {
+    intervals.sort();
+    let mut merged: Vec<(BigUint, BigUint)> = Vec::with_capacity(intervals.len());
+    for (a, b) in intervals {
+        match merged.last_mut() {
+            Some((_, last_b)) if a <= &*last_b + 1u32 => {
+                if b > *last_b {
+                    *last_b = b;
+                }
+            }
+            _ => merged.push((a, b)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod rsa_attack_bleichenbacher_tests {
+    use super::*;
+
+    /// counts the queries made of an oracle
+    struct Counting<O> {
+        oracle: O,
+        queries: usize,
+    }
+
+    impl<O: PaddingOracle> PaddingOracle for Counting<O> {
+        type Error = O::Error;
+
+        fn is_conforming(&mut self, c: &BigUint) -> Result<bool, O::Error> {
+            self.queries += 1;
+            self.oracle.is_conforming(c)
+        }
+    }
+
+    /// an oracle that never reports a conforming block
+    struct Never;
+
+    impl PaddingOracle for Never {
+        type Error = ();
+
+        fn is_conforming(&mut self, _: &BigUint) -> Result<bool, ()> {
+            Ok(false)
+        }
+    }
+
+    fn assert_recovers_message(keysize: usize, m: &[u8]) {
+        let (pk, sk) = rsa::gen_keypair(keysize);
+        let mut oracle = Counting {
+            oracle: PaddingServer::new(sk),
+            queries: 0,
+        };
+        let c = rsa::encrypt_pkcs1v15(m, &pk).unwrap();
+
+        let em = recover_message(&mut oracle, &pk, &c).unwrap();
+        let em = rsa::decode_i2osp(&em, rsa::byte_len(&pk.n));
+        assert_eq!(&em[..2], &[0x00, 0x02]);
+        assert!(em.ends_with(m));
+        assert!(oracle.queries > 1);
+    }
+
+    #[test]
+    fn merging_intervals() {
+        let interval = |a: u32, b: u32| (BigUint::from(a), BigUint::from(b));
+        assert_eq!(
+            merge(vec![
+                interval(10, 12),
+                interval(1, 3),
+                interval(4, 5),
+                interval(11, 20),
+                interval(7, 8)
+            ]),
+            vec![interval(1, 5), interval(7, 8), interval(10, 20)]
+        );
+    }
+
+    #[test]
+    fn giving_up_on_oracle_that_never_answers_true() {
+        // a 3-byte modulus, for which B = 2^8
+        let pk = PublicKey {
+            e: BigUint::from(3u32),
+            n: BigUint::from(70_001u32),
+        };
+        let c = BigUint::from(42u32);
+        assert_eq!(
+            recover_message(&mut Never, &pk, &c),
+            Err(Error::InconsistentOracle)
+        );
+
+        // the searches of step 2, reached with an oracle that stops answering true
+        let bounds = Bounds {
+            two_b: BigUint::from(512u32),
+            three_b: BigUint::from(768u32),
+        };
+        let mut attack = Attack {
+            oracle: &mut Never,
+            pk: &pk,
+            c0: c,
+        };
+        assert_eq!(
+            attack.search_from(BigUint::from(92u32)),
+            Err(Error::InconsistentOracle)
+        );
+        let (a, b) = (BigUint::from(699u32), BigUint::from(700u32));
+        assert_eq!(
+            attack.search_near(&a, &b, &BigUint::from(92u32), &bounds),
+            Err(Error::InconsistentOracle)
+        );
+    }
+
+    #[test]
+    fn recovering_message_with_256_bit_key() {
+        assert_recovers_message(256, b"kick it, CC");
+    }
+
+    #[test]
+    fn recovering_message_with_512_bit_key() {
+        assert_recovers_message(512, b"kick it, CC");
+    }
+
+    // tens of thousands of queries (more or fewer with luck): run with `cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    fn recovering_message_with_1024_bit_key() {
+        assert_recovers_message(1024, b"kick it, CC");
+    }
+}
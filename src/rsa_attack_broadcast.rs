@@ -0,0 +1,182 @@
+use crate::number_theory;
+use crate::rsa::PublicKey;
+use num::traits::{One, ToPrimitive};
+use num_bigint_dig::BigUint;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// fewer cyphertexts than the public exponent were supplied
+    TooFewCyphertexts { needed: usize, supplied: usize },
+    /// the cyphertexts weren't all encrypted with the same public exponent
+    MismatchedExponents,
+    /// two moduli share a factor (so CRT doesn't apply, but both keys are broken anyway)
+    SharedFactor,
+    /// the combined cyphertext has no exact e-th root, so the same message was not
+    /// encrypted under every key (or it was padded with different randomness)
+    NoExactRoot,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::TooFewCyphertexts { needed, supplied } => write!(
+                f,
+                "{} cyphertexts needed, only {} supplied",
+                needed, supplied
+            ),
+            Error::MismatchedExponents => write!(f, "public exponents differ"),
+            Error::SharedFactor => write!(f, "moduli are not pairwise coprime"),
+            Error::NoExactRoot => write!(f, "no exact root"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*******************************
+ * HÅSTAD'S BROADCAST ATTACK
+ *******************************/
+
+/// Recovers message m from the cyphertexts of m encrypted (without padding) under
+/// at least e different public keys sharing public exponent e (cf: cryptopals #40).
+///
+/// Since m < every n_i, m^e < n_1 * n_2 * ... * n_e. So combining the c_i = m^e mod n_i
+/// with the Chinese Remainder Theorem yields m^e mod (n_1 * ... * n_e) = m^e itself,
+/// whose (ordinary, integer) e-th root is m.
+///
+/// See: https://en.wikipedia.org/wiki/Coppersmith%27s_attack#H%C3%A5stad's_broadcast_attack
+pub fn recover_broadcast_message(cyphertexts: &[(BigUint, PublicKey)]) -> Result<BigUint, Error> {
+    let no_cyphertexts = Error::TooFewCyphertexts {
+        needed: 1,
+        supplied: 0,
+    };
+    let e = &cyphertexts.first().ok_or(no_cyphertexts)?.1.e;
+    if cyphertexts.iter().any(|(_, pk)| &pk.e != e) {
+        return Err(Error::MismatchedExponents);
+    }
+    let needed = e.to_usize().unwrap_or(usize::MAX);
+    if cyphertexts.len() < needed {
+        return Err(Error::TooFewCyphertexts {
+            needed,
+            supplied: cyphertexts.len(),
+        });
+    }
+
+    let residues = cyphertexts[..needed]
+        .iter()
+        .map(|(c, pk)| (c.clone(), pk.n.clone()))
+        .collect::<Vec<_>>();
+    let product = residues.iter().fold(BigUint::one(), |acc, (_, n)| acc * n);
+    let m_e = match number_theory::crt(&residues) {
+        Some((m_e, lcm)) if lcm == product => m_e,
+        _ => return Err(Error::SharedFactor),
+    };
+    number_theory::exact_kth_root(&m_e, needed as u32).ok_or(Error::NoExactRoot)
+}
+
+#[cfg(test)]
+mod rsa_attack_broadcast_tests {
+    use super::*;
+    use crate::rsa;
+
+    fn gen_keypairs(count: usize, e: u32) -> Vec<(PublicKey, rsa::SecretKey)> {
+        (0..count)
+            .map(|_| rsa::gen_keypair_with_exponent(512, &BigUint::from(e)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn recovering_message_broadcast_under_three_keys() {
+        let m = BigUint::from_bytes_be(b"attack at dawn, rendezvous at the old mill");
+        let cyphertexts = gen_keypairs(3, 3)
+            .into_iter()
+            .map(|(pk, _)| (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk))
+            .collect::<Vec<_>>();
+
+        assert_eq!(recover_broadcast_message(&cyphertexts), Ok(m));
+    }
+
+    #[test]
+    fn recovering_message_broadcast_under_more_keys_than_needed() {
+        let m = BigUint::from_bytes_be(b"YELLOW SUBMARINE");
+        let cyphertexts = gen_keypairs(4, 3)
+            .into_iter()
+            .map(|(pk, _)| (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk))
+            .collect::<Vec<_>>();
+
+        assert_eq!(recover_broadcast_message(&cyphertexts), Ok(m));
+    }
+
+    #[test]
+    fn failing_to_recover_message_broadcast_under_too_few_keys() {
+        let m = BigUint::from_bytes_be(b"YELLOW SUBMARINE");
+        let cyphertexts = gen_keypairs(2, 3)
+            .into_iter()
+            .map(|(pk, _)| (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recover_broadcast_message(&cyphertexts),
+            Err(Error::TooFewCyphertexts {
+                needed: 3,
+                supplied: 2
+            })
+        );
+    }
+
+    #[test]
+    fn failing_to_recover_different_messages() {
+        let cyphertexts = gen_keypairs(3, 3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (pk, _))| {
+                let m = BigUint::from(1_000_000u32 + i as u32);
+                (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recover_broadcast_message(&cyphertexts),
+            Err(Error::NoExactRoot)
+        );
+    }
+
+    #[test]
+    fn rejecting_mismatched_exponents() {
+        let m = BigUint::from(42u32);
+        let mut keypairs = gen_keypairs(2, 3);
+        keypairs.extend(gen_keypairs(1, 5));
+        let cyphertexts = keypairs
+            .into_iter()
+            .map(|(pk, _)| (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recover_broadcast_message(&cyphertexts),
+            Err(Error::MismatchedExponents)
+        );
+    }
+
+    #[test]
+    fn rejecting_moduli_sharing_a_factor() {
+        let m = BigUint::from(42u32);
+        let mut keypairs = gen_keypairs(2, 3);
+        let sk = rsa::SecretKey::from_primes(
+            keypairs[0].1.p.clone(),
+            keypairs[1].1.q.clone(),
+            BigUint::from(3u32),
+        )
+        .unwrap();
+        keypairs.push((sk.public_key(), sk));
+        let cyphertexts = keypairs
+            .into_iter()
+            .map(|(pk, _)| (rsa::encrypt_unpadded(&m, &pk).unwrap(), pk))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recover_broadcast_message(&cyphertexts),
+            Err(Error::SharedFactor)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::number_theory;
+use crate::rsa::PublicKey;
+use num::traits::{One, Signed};
+use num_bigint_dig::{BigInt, BigUint, Sign, ToBigInt};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// the public keys don't share a modulus
+    DifferentModuli,
+    /// the public exponents have a common factor, so no combination of them is 1
+    ExponentsNotCoprime,
+    /// a cyphertext shares a factor with the modulus (which the key's owner should worry about)
+    NotInvertible,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::DifferentModuli => write!(f, "moduli differ"),
+            Error::ExponentsNotCoprime => write!(f, "public exponents are not coprime"),
+            Error::NotInvertible => write!(f, "cyphertext is not invertible"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*******************************
+ * COMMON MODULUS ATTACK
+ *******************************/
+
+/// Recovers message m from its (unpadded) cyphertexts `c1` and `c2` under two public keys
+/// sharing a modulus n, but with coprime public exponents e1 and e2.
+///
+/// The extended euclidian algorithm gives a and b (one of them negative) such that
+/// a * e1 + b * e2 = 1, so that c1^a * c2^b = m^(a * e1 + b * e2) = m mod n.
+///
+/// See: https://crypto.stackexchange.com/questions/16283/how-to-use-common-modulus-attack
+pub fn recover_message(
+    c1: &BigUint,
+    pk1: &PublicKey,
+    c2: &BigUint,
+    pk2: &PublicKey,
+) -> Result<BigUint, Error> {
+    if pk1.n != pk2.n {
+        return Err(Error::DifferentModuli);
+    }
+    let n = &pk1.n;
+    let (e1, e2) = (pk1.e.to_bigint().unwrap(), pk2.e.to_bigint().unwrap());
+    let (g, a, b) = number_theory::egcd(&e1, &e2);
+    if !g.is_one() {
+        return Err(Error::ExponentsNotCoprime);
+    }
+    Ok((modpow_signed(c1, &a, n)? * modpow_signed(c2, &b, n)?) % n)
+}
+
+/// `base`^`exp` mod `modulus`, for `exp` of either sign
+fn modpow_signed(base: &BigUint, exp: &BigInt, modulus: &BigUint) -> Result<BigUint, Error> {
+    let base = match exp.sign() {
+        Sign::Minus => number_theory::mod_inverse(base, modulus).ok_or(Error::NotInvertible)?,
+        _ => base.clone(),
+    };
+    Ok(base.modpow(&exp.abs().to_biguint().unwrap(), modulus))
+}
+
+#[cfg(test)]
+mod rsa_attack_common_modulus_tests {
+    use super::*;
+    use crate::rsa;
+    use crate::rsa::SecretKey;
+
+    /// two public keys sharing a modulus, with public exponents 3 and 65537
+    fn gen_public_keys_sharing_modulus() -> (PublicKey, PublicKey) {
+        loop {
+            let (pk1, sk) = rsa::gen_keypair_with_exponent(512, &BigUint::from(3u32)).unwrap();
+            if let Some(sk2) = SecretKey::from_primes(sk.p, sk.q, rsa::DEFAULT_EXPONENT.into()) {
+                break (pk1, sk2.public_key());
+            }
+        }
+    }
+
+    #[test]
+    fn recovering_message_encrypted_under_common_modulus() {
+        let (pk1, pk2) = gen_public_keys_sharing_modulus();
+        let m = BigUint::from_bytes_be(b"Beware the Jabberwock, my son!");
+        let c1 = rsa::encrypt_unpadded(&m, &pk1).unwrap();
+        let c2 = rsa::encrypt_unpadded(&m, &pk2).unwrap();
+
+        assert_eq!(recover_message(&c1, &pk1, &c2, &pk2), Ok(m.clone()));
+        assert_eq!(recover_message(&c2, &pk2, &c1, &pk1), Ok(m));
+    }
+
+    #[test]
+    fn rejecting_keys_without_common_modulus_or_coprime_exponents() {
+        let (pk1, pk2) = gen_public_keys_sharing_modulus();
+        let (pk3, _) = rsa::gen_keypair(512);
+        let m = BigUint::from(42u32);
+        let c1 = rsa::encrypt_unpadded(&m, &pk1).unwrap();
+        let c2 = rsa::encrypt_unpadded(&m, &pk2).unwrap();
+        let c3 = rsa::encrypt_unpadded(&m, &pk3).unwrap();
+
+        assert_eq!(
+            recover_message(&c2, &pk2, &c3, &pk3),
+            Err(Error::DifferentModuli)
+        );
+        assert_eq!(
+            recover_message(&c1, &pk1, &c1, &pk1),
+            Err(Error::ExponentsNotCoprime)
+        );
+    }
+}
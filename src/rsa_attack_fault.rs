@@ -0,0 +1,120 @@
+use crate::rsa::{self, PublicKey, SecretKey};
+use num::integer::gcd;
+use num::traits::One;
+use num_bigint_dig::BigUint;
+use rand::rngs::OsRng;
+use rand::Rng;
+
+/// Anything that will sign (ie: raise to the private exponent) messages on request:
+/// an in-process `FaultySigner`, or a client for some remote service or device.
+pub trait Signer {
+    type Error;
+
+    fn sign(&mut self, m: &BigUint) -> Result<BigUint, Self::Error>;
+}
+
+/*******************************
+ * FAULTY SIGNER
+ *******************************/
+
+/// A signer computing signatures via the Chinese Remainder Theorem on faulty hardware:
+/// every exponentiation mod q has a random bit of its result flipped, while the one mod p
+/// comes out right. With verification on, it checks each signature against the public
+/// exponent (as `rsa` does) and withholds the faulty ones.
+pub struct FaultySigner {
+    sk: SecretKey,
+    verify: bool,
+}
+
+impl FaultySigner {
+    pub fn new(sk: SecretKey, verify: bool) -> FaultySigner {
+        FaultySigner { sk, verify }
+    }
+}
+
+impl Signer for FaultySigner {
+    type Error = rsa::Error;
+
+    fn sign(&mut self, m: &BigUint) -> Result<BigUint, rsa::Error> {
+        let sk = &self.sk;
+        if m >= &sk.n {
+            return Err(rsa::Error::MessageTooLong);
+        }
+        let s_p = m.modpow(&sk.d_p, &sk.p);
+        let bit = OsRng::new()
+            .expect("Failed to build RNG")
+            .gen_range(0, sk.q.bits());
+        let s_q = (m.modpow(&sk.d_q, &sk.q) ^ (BigUint::one() << bit)) % &sk.q;
+        let s = rsa::combine_crt(&s_p, &s_q, sk);
+        match !self.verify || &s.modpow(&sk.e, &sk.n) == m {
+            true => Ok(s),
+            false => Err(rsa::Error::Fault),
+        }
+    }
+}
+
+/*******************************
+ * BELLCORE ATTACK
+ *******************************/
+
+/// Recovers the secret key from a single signature `s` of `m` (the integer actually raised
+/// to the private exponent, eg: a PKCS#1 v1.5 encoded block) under `pk` that was computed via
+/// the CRT with a fault in one half, as described in (Lenstra's variant of) Boneh, DeMillo
+/// and Lipton's attack: https://link.springer.com/content/pdf/10.1007/s001450010016.pdf
+///
+/// If s is right mod p but wrong mod q, then s^e = m mod p but not mod q, so p divides
+/// s^e - m but n doesn't, and gcd(s^e - m, n) = p. Returns `None` if `s` is a valid
+/// signature (or wrong mod both primes), since the gcd is then n (or 1).
+pub fn recover_secret_key(pk: &PublicKey, m: &BigUint, s: &BigUint) -> Option<SecretKey> {
+    let s_e = s.modpow(&pk.e, &pk.n);
+    let diff = (&s_e + &pk.n - (m % &pk.n)) % &pk.n;
+    let p = gcd(diff, pk.n.clone());
+    if p.is_one() || p == pk.n {
+        return None;
+    }
+    let q = &pk.n / &p;
+    SecretKey::from_primes(p, q, pk.e.clone())
+}
+
+#[cfg(test)]
+mod rsa_attack_fault_tests {
+    use super::*;
+    use crate::hashing::HashFunction;
+
+    #[test]
+    fn recovering_secret_key_from_faulty_signature() {
+        let (pk, sk) = rsa::gen_keypair(1024);
+        let d = sk.d.clone();
+        let mut signer = FaultySigner::new(sk, false);
+        let m = BigUint::from_bytes_be(b"pay Mallory $1,000,000");
+        let s = signer.sign(&m).unwrap();
+
+        assert_ne!(s.modpow(&pk.e, &pk.n), m);
+        assert_eq!(recover_secret_key(&pk, &m, &s).map(|sk| sk.d), Some(d));
+    }
+
+    #[test]
+    fn withholding_faulty_signatures_with_verification() {
+        let (_, sk) = rsa::gen_keypair(1024);
+        let m = BigUint::from_bytes_be(b"pay Mallory $1,000,000");
+        let mut signer = FaultySigner::new(sk, true);
+        assert_eq!(signer.sign(&m), Err(rsa::Error::Fault));
+
+        // as does `rsa`, when the key itself is corrupted
+        let (_, mut sk) = rsa::gen_keypair(1024);
+        sk.d_q += 1u32;
+        assert_eq!(
+            rsa::sign_pkcs1v15(b"pay Mallory", &sk, HashFunction::Sha256),
+            Err(rsa::Error::Fault)
+        );
+    }
+
+    #[test]
+    fn failing_to_recover_secret_key_from_sound_signature() {
+        let (pk, sk) = rsa::gen_keypair(1024);
+        let m = BigUint::from_bytes_be(b"pay Mallory $1,000,000");
+        let s = rsa::decrypt_unpadded(&m, &sk).unwrap();
+
+        assert!(recover_secret_key(&pk, &m, &s).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::number_theory;
+use crate::rsa::PublicKey;
+use num::traits::{ToPrimitive, Zero};
+use num_bigint_dig::BigUint;
+use std::fmt;
+
+/// The largest public exponent the attack is attempted for: the polynomials have degree e,
+/// and their gcd takes about e^2 multiplications modulo n
+pub const MAX_EXPONENT: usize = 1 << 10;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// the public exponent is larger than `MAX_EXPONENT`
+    ExponentTooLarge,
+    /// a leading coefficient shares a factor with the modulus (which the key's owner
+    /// should worry about)
+    NotInvertible,
+    /// the polynomials have no common linear factor, so the messages are not related as given
+    /// (or are related trivially, with m2 = m1 or a = 0)
+    NoLinearFactor,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ExponentTooLarge => write!(f, "public exponent too large"),
+            Error::NotInvertible => write!(f, "leading coefficient is not invertible"),
+            Error::NoLinearFactor => write!(f, "no common linear factor"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*******************************
+ * FRANKLIN-REITER ATTACK
+ *******************************/
+
+/// Recovers message m1 from its (unpadded) cyphertext `c1` and the cyphertext `c2` of a
+/// related message m2 = `a` * m1 + `b` mod n, both encrypted under `pk` (with a small
+/// public exponent e), as described in:
+/// https://www.cs.unc.edu/~reiter/papers/1996/Eurocrypt.pdf
+///
+/// m1 is a root of both g1(x) = x^e - c1 and g2(x) = (a * x + b)^e - c2 over Z_n, so
+/// x - m1 divides both. For e = 3 (and in general, though not always, for small e) it
+/// is their greatest common divisor, which the euclidian algorithm finds.
+pub fn recover_related_message(
+    pk: &PublicKey,
+    c1: &BigUint,
+    c2: &BigUint,
+    a: &BigUint,
+    b: &BigUint,
+) -> Result<BigUint, Error> {
+    let n = &pk.n;
+    let e = match pk.e.to_usize() {
+        Some(e) if e <= MAX_EXPONENT => e,
+        _ => return Err(Error::ExponentTooLarge),
+    };
+    if (a % n).is_zero() {
+        return Err(Error::NoLinearFactor);
+    }
+
+    let mut g1 = vec![BigUint::zero(); e + 1];
+    g1[0] = (n - c1 % n) % n;
+    g1[e] = 1u32.into();
+
+    let linear = vec![b % n, a % n];
+    let mut g2 = vec![1u32.into()];
+    for _ in 0..e {
+        g2 = poly_mul(&g2, &linear, n);
+    }
+    g2[0] = (&g2[0] + n - c2 % n) % n;
+
+    match make_monic(poly_gcd(g1, trim(g2), n)?, n)?.as_slice() {
+        // x + c0, whose root is -c0
+        [c0, _] => Ok((n - c0) % n),
+        _ => Err(Error::NoLinearFactor),
+    }
+}
+
+/*******************************
+ * POLYNOMIALS OVER Z_n
+ *******************************/
+
+// polynomials are vectors of coefficients (lowest degree first) without trailing zeros
+
+fn trim(mut poly: Vec<BigUint>) -> Vec<BigUint> {
+    while poly.last().map(Zero::is_zero).unwrap_or(false) {
+        poly.pop();
+    }
+    poly
+}
+
+fn poly_mul(f: &[BigUint], g: &[BigUint], n: &BigUint) -> Vec<BigUint> {
+    let mut product = vec![BigUint::zero(); f.len() + g.len() - 1];
+    for (i, f_i) in f.iter().enumerate() {
+        for (j, g_j) in g.iter().enumerate() {
+            product[i + j] = (&product[i + j] + f_i * g_j) % n;
+        }
+    }
+    trim(product)
+}
+
+/// the remainder of `f` divided by `g` (non-zero)
+fn poly_rem(mut f: Vec<BigUint>, g: &[BigUint], n: &BigUint) -> Result<Vec<BigUint>, Error> {
+    let lead_inv = number_theory::mod_inverse(&g[g.len() - 1], n).ok_or(Error::NotInvertible)?;
+    while f.len() >= g.len() {
+        let shift = f.len() - g.len();
+        let coef = (&f[f.len() - 1] * &lead_inv) % n;
+        for (i, g_i) in g.iter().enumerate() {
+            f[shift + i] = (&f[shift + i] + n - (&coef * g_i) % n) % n;
+        }
+        f = trim(f);
+    }
+    Ok(f)
+}
+
+fn poly_gcd(mut f: Vec<BigUint>, mut g: Vec<BigUint>, n: &BigUint) -> Result<Vec<BigUint>, Error> {
+    while !g.is_empty() {
+        let r = poly_rem(f, &g, n)?;
+        f = std::mem::replace(&mut g, r);
+    }
+    Ok(f)
+}
+
+fn make_monic(f: Vec<BigUint>, n: &BigUint) -> Result<Vec<BigUint>, Error> {
+    let lead_inv = match f.last() {
+        Some(lead) => number_theory::mod_inverse(lead, n).ok_or(Error::NotInvertible)?,
+        None => return Ok(f),
+    };
+    Ok(f.into_iter().map(|f_i| (f_i * &lead_inv) % n).collect())
+}
+
+#[cfg(test)]
+mod rsa_attack_franklin_reiter_tests {
+    use super::*;
+    use crate::rsa;
+
+    #[test]
+    fn dividing_polynomials() {
+        let n = BigUint::from(101u32);
+        let poly = |coefs: &[u32]| coefs.iter().map(|&c| BigUint::from(c)).collect::<Vec<_>>();
+
+        // (x + 2) * (x + 3) = x^2 + 5 * x + 6
+        assert_eq!(
+            poly_mul(&poly(&[2, 1]), &poly(&[3, 1]), &n),
+            poly(&[6, 5, 1])
+        );
+        // x^2 + 5 * x + 7 = (x + 2) * (x + 3) + 1
+        assert_eq!(
+            poly_rem(poly(&[7, 5, 1]), &poly(&[2, 1]), &n),
+            Ok(poly(&[1]))
+        );
+        // x^2 + 5 * x + 6 and x^2 + 3 * x + 2 = (x + 1) * (x + 2)
+        let gcd = poly_gcd(poly(&[6, 5, 1]), poly(&[2, 3, 1]), &n).unwrap();
+        assert_eq!(make_monic(gcd, &n), Ok(poly(&[2, 1])));
+    }
+
+    #[test]
+    fn recovering_related_messages() {
+        let (pk, _) = rsa::gen_keypair_with_exponent(512, &BigUint::from(3u32)).unwrap();
+        let m1 = BigUint::from_bytes_be(b"the dog barks at midnight");
+        let (a, b) = (BigUint::from(1u32), BigUint::from(1u32 << 20));
+        let m2 = (&a * &m1 + &b) % &pk.n;
+        let c1 = rsa::encrypt_unpadded(&m1, &pk).unwrap();
+        let c2 = rsa::encrypt_unpadded(&m2, &pk).unwrap();
+
+        assert_eq!(
+            recover_related_message(&pk, &c1, &c2, &a, &b),
+            Ok(m1.clone())
+        );
+
+        let a = BigUint::from(12_345u32);
+        let m2 = (&a * &m1 + &b) % &pk.n;
+        let c2 = rsa::encrypt_unpadded(&m2, &pk).unwrap();
+        assert_eq!(recover_related_message(&pk, &c1, &c2, &a, &b), Ok(m1));
+    }
+
+    #[test]
+    fn failing_to_recover_unrelated_messages() {
+        let (pk, _) = rsa::gen_keypair_with_exponent(512, &BigUint::from(3u32)).unwrap();
+        let c1 = rsa::encrypt_unpadded(&BigUint::from(42u32), &pk).unwrap();
+        let c2 = rsa::encrypt_unpadded(&BigUint::from(4242u32), &pk).unwrap();
+        let one = BigUint::from(1u32);
+
+        assert_eq!(
+            recover_related_message(&pk, &c1, &c2, &one, &one),
+            Err(Error::NoLinearFactor)
+        );
+        // m2 = m1: every root of x^e - c1 is a common root
+        assert_eq!(
+            recover_related_message(&pk, &c1, &c1, &one, &0u32.into()),
+            Err(Error::NoLinearFactor)
+        );
+
+        let (pk, _) = rsa::gen_keypair(512);
+        assert_eq!(
+            recover_related_message(&pk, &c1, &c2, &one, &one),
+            Err(Error::ExponentTooLarge)
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::rsa::{self, PublicKey, SecretKey};
+use num::integer::Integer;
+use num::traits::{One, Zero};
+use num_bigint_dig::BigUint;
+
+/// Anything that reveals whether unpadded RSA cyphertexts decrypt to even or odd
+/// numbers: an in-process `ParityServer`, or a client for some remote service.
+pub trait ParityOracle {
+    type Error;
+
+    fn is_even(&mut self, c: &BigUint) -> Result<bool, Self::Error>;
+}
+
+/// A server that decrypts cyphertexts and reports (only) the parity of the result
+pub struct ParityServer {
+    sk: SecretKey,
+}
+
+impl ParityServer {
+    pub fn new(sk: SecretKey) -> ParityServer {
+        ParityServer { sk }
+    }
+}
+
+impl ParityOracle for ParityServer {
+    type Error = rsa::Error;
+
+    fn is_even(&mut self, c: &BigUint) -> Result<bool, rsa::Error> {
+        rsa::decrypt_unpadded(c, &self.sk).map(|m| m.is_even())
+    }
+}
+
+/*******************************
+ * PARITY ORACLE ATTACK
+ *******************************/
+
+/// Recovers the message of cyphertext `c` (encrypted under `pk`) from an oracle revealing
+/// the parity of decryptions (cf: cryptopals #46). See `recover_message_with_progress`.
+pub fn recover_message<O: ParityOracle>(
+    oracle: &mut O,
+    pk: &PublicKey,
+    c: &BigUint,
+) -> Result<BigUint, O::Error> {
+    recover_message_with_progress(oracle, pk, c, |_| ())
+}
+
+/// Recovers the message of cyphertext `c` (encrypted under `pk`) from an oracle revealing
+/// the parity of decryptions, calling `progress` with the upper bound on the message
+/// after each query (so that, printed, the message comes into focus from the left).
+///
+/// Multiplying `c` by 2^e doubles the message. Since n is odd, 2 * m mod n is even iff
+/// 2 * m didn't wrap around n, ie: iff m < n / 2. Doubling again (c * 4^e) tells whether
+/// m lies in the lower or upper half of that half, and so on: after i queries,
+///
+/// lo * n / 2^i <= m < (lo + 1) * n / 2^i
+///
+/// for some integer lo. The bounds are kept as exact fractions (the integer lo over 2^i)
+/// rather than rounded each step, so that after log2(n) queries, when the interval is
+/// narrower than 1, the one integer in it is the message down to the last byte.
+pub fn recover_message_with_progress<O, F>(
+    oracle: &mut O,
+    pk: &PublicKey,
+    c: &BigUint,
+    mut progress: F,
+) -> Result<BigUint, O::Error>
+where
+    O: ParityOracle,
+    F: FnMut(&BigUint),
+{
+    let doubler = BigUint::from(2u32).modpow(&pk.e, &pk.n);
+    let mut c = c.clone();
+    let mut lo = BigUint::zero();
+    let mut denominator = BigUint::one();
+    for _ in 0..pk.n.bits() {
+        c = (c * &doubler) % &pk.n;
+        lo <<= 1;
+        denominator <<= 1;
+        if !oracle.is_even(&c)? {
+            lo += 1u32;
+        }
+        progress(&((&lo + 1u32) * &pk.n / &denominator));
+    }
+    // the least integer >= lo * n / 2^i
+    Ok((lo * &pk.n + &denominator - 1u32) / denominator)
+}
+
+#[cfg(test)]
+mod rsa_attack_parity_tests {
+    use super::*;
+
+    #[test]
+    fn reporting_parity_of_decryptions() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = ParityServer::new(sk);
+        let encrypt = |m: u32| rsa::encrypt_unpadded(&BigUint::from(m), &pk).unwrap();
+
+        assert_eq!(server.is_even(&encrypt(42)), Ok(true));
+        assert_eq!(server.is_even(&encrypt(43)), Ok(false));
+    }
+
+    #[test]
+    fn recovering_message_from_parity_oracle() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = ParityServer::new(sk);
+
+        for m in &[
+            BigUint::from_bytes_be(b"Funky Cold Medina"),
+            BigUint::zero(),
+            BigUint::one(),
+            &pk.n - 1u32,
+        ] {
+            let c = rsa::encrypt_unpadded(m, &pk).unwrap();
+            assert_eq!(recover_message(&mut server, &pk, &c).as_ref(), Ok(m));
+        }
+    }
+
+    #[test]
+    fn reporting_progress_of_converging_upper_bound() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = ParityServer::new(sk);
+        let m = BigUint::from_bytes_be(b"Funky Cold Medina");
+        let c = rsa::encrypt_unpadded(&m, &pk).unwrap();
+
+        let mut bounds = vec![];
+        let m_ = recover_message_with_progress(&mut server, &pk, &c, |hi| bounds.push(hi.clone()));
+
+        assert_eq!(m_, Ok(m.clone()));
+        assert_eq!(bounds.len(), 512);
+        assert!(bounds.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(bounds.iter().all(|hi| hi >= &m));
+    }
+}
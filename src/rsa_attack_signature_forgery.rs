@@ -0,0 +1,124 @@
+use crate::hashing::HashFunction;
+use crate::rsa::{self, pkcs1v15, PublicKey};
+use num::traits::ToPrimitive;
+use num_bigint_dig::BigUint;
+
+/*******************************
+ * SLOPPY VERIFIER
+ *******************************/
+
+/// Verifies a PKCS#1 v1.5 signature `s` of `m` the way too many implementations did:
+/// by scanning the block from the left for 00 01 FF ... FF 00, then the DigestInfo
+/// and digest, never checking that the digest is right-justified, ie: that nothing follows it.
+///
+/// Do not use this for anything but demonstrating `forge_signature`: use `rsa::verify_pkcs1v15`.
+pub fn verify_pkcs1v15_sloppily(
+    m: &[u8],
+    s: &BigUint,
+    pk: &PublicKey,
+    hash: HashFunction,
+) -> Result<(), rsa::Error> {
+    let em = rsa::decode_i2osp(&s.modpow(&pk.e, &pk.n), rsa::byte_len(&pk.n));
+    let padding_len = em.iter().skip(2).take_while(|&&b| b == 0xff).count();
+    let digest_info = &em[(2 + padding_len)..];
+
+    let expected = [
+        &[0x00][..],
+        pkcs1v15::digest_info_prefix(hash),
+        &hash.digest(m)[..],
+    ]
+    .concat();
+    match em[..2] == [0x00, 0x01] && padding_len > 0 && digest_info.starts_with(&expected) {
+        true => Ok(()),
+        false => Err(rsa::Error::InvalidSignature),
+    }
+}
+
+/*******************************
+ * SIGNATURE FORGERY
+ *******************************/
+
+/// Forges a signature of `m`, under any key with a small public exponent e, that
+/// `verify_pkcs1v15_sloppily` accepts (cf: cryptopals #42, and Bleichenbacher '06).
+///
+/// The forged block is 00 01 FF 00 DigestInfo || garbage, where the garbage
+/// absorbs the difference between the block and the e-th power of its (integer) e-th root.
+/// With the garbage set to FF ... FF, the root s satisfies:
+///
+/// block - (3 * s^(e - 1) + ...) < s^e <= block
+///
+/// so s^e still starts with 00 01 FF 00 DigestInfo as long as the garbage is long enough
+/// (for e = 3, roughly two thirds of the modulus). Otherwise, returns `None`.
+pub fn forge_signature(m: &[u8], pk: &PublicKey, hash: HashFunction) -> Option<BigUint> {
+    let k = rsa::byte_len(&pk.n);
+    let mut em = [
+        &[0x00, 0x01, 0xff, 0x00][..],
+        pkcs1v15::digest_info_prefix(hash),
+        &hash.digest(m)[..],
+    ]
+    .concat();
+    if em.len() > k {
+        return None;
+    }
+    em.resize(k, 0xff);
+
+    // a block starting 00 01 is less than n, so no modular reduction gets in the way
+    let s = rsa::encode_os2ip(&em).nth_root(pk.e.to_u32()?);
+    match verify_pkcs1v15_sloppily(m, &s, pk, hash) {
+        Ok(()) => Some(s),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod rsa_attack_signature_forgery_tests {
+    use super::*;
+
+    lazy_static! {
+        static ref PK_E3: PublicKey = rsa::gen_keypair_with_exponent(1024, &BigUint::from(3u32))
+            .unwrap()
+            .0;
+    }
+
+    #[test]
+    fn verifying_genuine_signatures_sloppily() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let s = rsa::sign_pkcs1v15(b"hi mom", &sk, HashFunction::Sha1).unwrap();
+
+        assert_eq!(
+            verify_pkcs1v15_sloppily(b"hi mom", &s, &pk, HashFunction::Sha1),
+            Ok(())
+        );
+        assert_eq!(
+            verify_pkcs1v15_sloppily(b"hi dad", &s, &pk, HashFunction::Sha1),
+            Err(rsa::Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn forging_signatures_accepted_only_by_sloppy_verifier() {
+        let pk = &*PK_E3;
+        let s = forge_signature(b"hi mom", pk, HashFunction::Sha1).unwrap();
+
+        assert_eq!(
+            verify_pkcs1v15_sloppily(b"hi mom", &s, pk, HashFunction::Sha1),
+            Ok(())
+        );
+        assert_eq!(
+            rsa::verify_pkcs1v15(b"hi mom", &s, pk, HashFunction::Sha1),
+            Err(rsa::Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn failing_to_forge_signatures_without_room_for_garbage() {
+        // a SHA-512 DigestInfo leaves too little room in a 1024-bit block
+        assert_eq!(
+            forge_signature(b"hi mom", &PK_E3, HashFunction::Sha512),
+            None
+        );
+
+        let (pk, _) = rsa::gen_keypair(512);
+        assert_eq!(forge_signature(b"hi mom", &pk, HashFunction::Sha1), None);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::hashing::HashFunction;
+use crate::number_theory;
+use crate::rsa::{self, PublicKey, SecretKey};
+use num_bigint_dig::{BigUint, RandBigInt};
+use rand::rngs::OsRng;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Anything that will decrypt unpadded RSA cyphertexts on request: an in-process
+/// `DecryptionServer`, or a client for some remote service.
+pub trait DecryptionOracle {
+    type Error;
+
+    fn decrypt(&mut self, c: &BigUint) -> Result<BigUint, Self::Error>;
+}
+
+/*******************************
+ * DECRYPTION SERVER
+ *******************************/
+
+#[derive(Debug, PartialEq)]
+pub enum ServerError {
+    /// the cyphertext was decrypted before, and won't be decrypted again
+    AlreadySeen,
+    Decryption(rsa::Error),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerError::AlreadySeen => write!(f, "cyphertext already decrypted"),
+            ServerError::Decryption(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// A server that decrypts any unpadded cyphertext it is sent, but only once: it keeps
+/// the hashes of the cyphertexts it has decrypted, and refuses to decrypt them again.
+pub struct DecryptionServer {
+    sk: SecretKey,
+    seen: HashSet<Vec<u8>>,
+}
+
+impl DecryptionServer {
+    pub fn new(sk: SecretKey) -> DecryptionServer {
+        DecryptionServer {
+            sk,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl DecryptionOracle for DecryptionServer {
+    type Error = ServerError;
+
+    fn decrypt(&mut self, c: &BigUint) -> Result<BigUint, ServerError> {
+        if !self
+            .seen
+            .insert(HashFunction::Sha256.digest(&c.to_bytes_be()))
+        {
+            return Err(ServerError::AlreadySeen);
+        }
+        rsa::decrypt_unpadded(c, &self.sk).map_err(ServerError::Decryption)
+    }
+}
+
+/*******************************
+ * MESSAGE RECOVERY ATTACK
+ *******************************/
+
+/// Recovers the message of cyphertext `c` (encrypted under `pk`) from an oracle that
+/// refuses to decrypt `c` itself, by having it decrypt a blinded cyphertext instead
+/// (cf: cryptopals #41).
+///
+/// With random s, c' = s^e * c mod n decrypts to p' = s * m mod n (since unpadded RSA
+/// is multiplicatively homomorphic), so m = p' * s^-1 mod n.
+pub fn recover_message<O: DecryptionOracle>(
+    oracle: &mut O,
+    pk: &PublicKey,
+    c: &BigUint,
+) -> Result<BigUint, O::Error> {
+    let (s, s_inv) = gen_invertible(&pk.n);
+    let c_prime = (s.modpow(&pk.e, &pk.n) * c) % &pk.n;
+    let p_prime = oracle.decrypt(&c_prime)?;
+    Ok((p_prime * s_inv) % &pk.n)
+}
+
+/// generates a random s > 1 invertible mod `n`, and its inverse
+fn gen_invertible(n: &BigUint) -> (BigUint, BigUint) {
+    let mut rng = OsRng::new().expect("Failed to build RNG");
+    loop {
+        let s = rng.gen_biguint_range(&BigUint::from(2u32), n);
+        if let Some(s_inv) = number_theory::mod_inverse(&s, n) {
+            break (s, s_inv);
+        }
+    }
+}
+
+#[cfg(test)]
+mod rsa_attack_unpadded_tests {
+    use super::*;
+
+    #[test]
+    fn refusing_to_decrypt_the_same_cyphertext_twice() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = DecryptionServer::new(sk);
+        let c = rsa::encrypt_unpadded(&BigUint::from(42u32), &pk).unwrap();
+
+        assert_eq!(server.decrypt(&c), Ok(BigUint::from(42u32)));
+        assert_eq!(server.decrypt(&c), Err(ServerError::AlreadySeen));
+    }
+
+    #[test]
+    fn recovering_message_from_blinded_cyphertext() {
+        let (pk, sk) = rsa::gen_keypair(512);
+        let mut server = DecryptionServer::new(sk);
+        let m = BigUint::from_bytes_be(b"{time: 1356304276, social: '555-55-5555'}");
+        let c = rsa::encrypt_unpadded(&m, &pk).unwrap();
+        server.decrypt(&c).unwrap();
+
+        assert_eq!(recover_message(&mut server, &pk, &c), Ok(m));
+    }
+}
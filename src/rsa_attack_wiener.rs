@@ -0,0 +1,124 @@
+use crate::rsa::{PublicKey, SecretKey};
+use num::integer::Integer;
+use num::traits::{CheckedSub, One, Zero};
+use num_bigint_dig::BigUint;
+
+/*******************************
+ * WIENER'S ATTACK
+ *******************************/
+
+/// Recovers the secret key (d, and the factors p and q of n) from public key `pk`
+/// when its private exponent is small, as described in:
+/// https://www.cits.ruhr-uni-bochum.de/imperia/md/content/may/krypto2ss08/shortsecretexponents.pdf
+///
+/// Since e * d = 1 + k * phi(n), and phi(n) is close to n, e / n is close to k / d. When
+/// d < n^0.25 / 3 it is so close that k / d is one of the convergents of the continued
+/// fraction of e / n. Each convergent gives a candidate phi(n) = (e * d - 1) / k, and with
+/// it p + q = n - phi(n) + 1, so p and q are the roots of x^2 - (p + q) * x + n.
+///
+/// Returns `None` if no convergent factors n, ie: if d is not below n^0.25 (or e and d
+/// are inverses modulo lambda(n) rather than phi(n), with lambda(n) much smaller). That
+/// doesn't make d safe: up to n^0.292, lattice methods (Boneh-Durfee) still recover it.
+pub fn recover_secret_key(pk: &PublicKey) -> Option<SecretKey> {
+    convergents(&pk.e, &pk.n)
+        .filter(|(k, _)| !k.is_zero())
+        .find_map(|(k, d)| {
+            let (phi, r) = (&pk.e * &d - 1u32).div_rem(&k);
+            match r.is_zero() {
+                true => factor(&pk.n, &phi),
+                false => None,
+            }
+        })
+        .and_then(|(p, q)| SecretKey::from_primes(p, q, pk.e.clone()))
+}
+
+/// Finds p and q (p > q) such that p * q = `n` and (p - 1) * (q - 1) = `phi`, if any
+fn factor(n: &BigUint, phi: &BigUint) -> Option<(BigUint, BigUint)> {
+    // p + q and (p - q)^2 = (p + q)^2 - 4 * p * q
+    let sum = (n + 1u32).checked_sub(phi)?;
+    let diff_squared = (&sum * &sum).checked_sub(&(n * 4u32))?;
+    let diff = diff_squared.sqrt();
+    if &diff * &diff != diff_squared || sum.is_odd() != diff.is_odd() {
+        return None;
+    }
+    let (p, q) = ((&sum + &diff) >> 1, (&sum - &diff) >> 1);
+    match q > BigUint::one() && &p * &q == *n {
+        true => Some((p, q)),
+        false => None,
+    }
+}
+
+/// The convergents (as numerator, denominator) of the continued fraction of `x / y`
+fn convergents(x: &BigUint, y: &BigUint) -> impl Iterator<Item = (BigUint, BigUint)> {
+    let (mut x, mut y) = (x.clone(), y.clone());
+    // the two previous convergents, starting from 0 / 1 and 1 / 0
+    let mut prev = (BigUint::zero(), BigUint::one());
+    let mut last = (BigUint::one(), BigUint::zero());
+    std::iter::from_fn(move || {
+        if y.is_zero() {
+            return None;
+        }
+        let (a, r) = x.div_rem(&y);
+        x = std::mem::replace(&mut y, r);
+        let next = (&a * &last.0 + &prev.0, &a * &last.1 + &prev.1);
+        prev = std::mem::replace(&mut last, next.clone());
+        Some(next)
+    })
+}
+
+#[cfg(test)]
+mod rsa_attack_wiener_tests {
+    use super::*;
+    use crate::number_theory;
+    use crate::rsa;
+    use num_bigint_dig::RandBigInt;
+    use rand::rngs::OsRng;
+
+    /// generates a keypair whose private exponent is a random `d_bits`-bit d
+    /// (and e its inverse modulo phi(n)), returning the key and d
+    fn gen_secret_key_with_small_d(keysize: usize, d_bits: usize) -> (SecretKey, BigUint) {
+        let (_, sk) = rsa::gen_keypair(keysize);
+        let phi = (&sk.p - 1u32) * (&sk.q - 1u32);
+        let mut rng = OsRng::new().expect("Failed to build RNG");
+        loop {
+            let d = rng.gen_biguint(d_bits) | (BigUint::one() << (d_bits - 1)) | BigUint::one();
+            if let Some(e) = number_theory::mod_inverse(&d, &phi) {
+                let sk = SecretKey::from_primes(sk.p.clone(), sk.q.clone(), e).unwrap();
+                break (sk, d);
+            }
+        }
+    }
+
+    #[test]
+    fn computing_convergents() {
+        // 649 / 200 = [3; 4, 12, 4]
+        let convergents = convergents(&BigUint::from(649u32), &BigUint::from(200u32));
+        let expected = [(3u32, 1u32), (13, 4), (159, 49), (649, 200)]
+            .iter()
+            .map(|&(h, k)| (BigUint::from(h), BigUint::from(k)));
+
+        assert!(convergents.eq(expected));
+    }
+
+    #[test]
+    fn recovering_small_private_exponent() {
+        // n^0.25 / 3 is a bit over 126 bits
+        let (sk, d) = gen_secret_key_with_small_d(512, 120);
+        let recovered = recover_secret_key(&sk.public_key()).unwrap();
+
+        assert_eq!(recovered.d, d);
+        assert_eq!(
+            (recovered.p, recovered.q),
+            (sk.p.clone().max(sk.q.clone()), sk.p.min(sk.q))
+        );
+    }
+
+    #[test]
+    fn failing_to_recover_large_private_exponent() {
+        let (sk, _) = gen_secret_key_with_small_d(512, 200);
+        assert!(recover_secret_key(&sk.public_key()).is_none());
+
+        let (pk, _) = rsa::gen_keypair(512);
+        assert!(recover_secret_key(&pk).is_none());
+    }
+}
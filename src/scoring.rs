@@ -12,7 +12,7 @@ impl ScoredCleartext {
     fn empty() -> ScoredCleartext {
         Self {
             cleartext: Vec::<u8>::new(),
-            score: std::f64::MAX,
+            score: f64::MAX,
         }
     }
 }
@@ -33,7 +33,7 @@ impl Ord for ScoredCleartext {
 
 impl PartialOrd for ScoredCleartext {
     fn partial_cmp(&self, other: &ScoredCleartext) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
@@ -73,7 +73,7 @@ impl Ord for ScoredCleartextBlocks {
 
 impl PartialOrd for ScoredCleartextBlocks {
     fn partial_cmp(&self, other: &ScoredCleartextBlocks) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
@@ -88,7 +88,7 @@ mod scoring_tests {
             ScoredCleartext::empty(),
             ScoredCleartext {
                 cleartext: Vec::<u8>::new(),
-                score: std::f64::MAX,
+                score: f64::MAX,
             }
         )
     }
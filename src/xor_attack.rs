@@ -1,3 +1,4 @@
+use crate::bytes::Bytes;
 use crate::characters::{CHARACTER_BYTES, FREQS_BY_CHAR, SUMMED_SQUARED_FREQUENCIES};
 use crate::encoding;
 use crate::scoring::ScoredCleartext;
@@ -18,27 +19,25 @@ use std::path::Path;
 /// as a potential key, and keeping the guess whose character frequency
 /// distribution most closely matches the ground truth distribution
 /// observed in English text
-pub fn brute_force_xor_cypher_from_hex(cyphertext: &Hex) -> String {
-    let cyphertext_bytes = encoding::hex2bytes(&cyphertext);
-    let cleartext_bytes = find_min_score_xor(&cyphertext_bytes).cleartext;
-    String::from_utf8_lossy(&cleartext_bytes).to_string()
+pub fn brute_force_xor_cypher_from_hex(cyphertext: &Hex) -> Bytes {
+    brute_force_decrypt(encoding::hex2bytes(cyphertext))
 }
 
-pub fn brute_force_decrypt(cyphertext_bytes: &Vec<u8>) -> String {
-    let cleartext_bytes = find_min_score_xor(cyphertext_bytes).cleartext;
-    String::from_utf8_lossy(&cleartext_bytes).to_string()
+pub fn brute_force_decrypt<T: AsRef<[u8]>>(cyphertext: T) -> Bytes {
+    Bytes(find_min_score_xor(cyphertext).cleartext)
 }
 
-pub fn find_min_score_xor(cyphertext_bytes: &Vec<u8>) -> ScoredCleartext {
+pub fn find_min_score_xor<T: AsRef<[u8]>>(cyphertext: T) -> ScoredCleartext {
+    let cyphertext_bytes = cyphertext.as_ref();
     CHARACTER_BYTES
         .par_iter()
-        .map(|key| evaluate_guess(cyphertext_bytes, key))
+        .map(|&key| evaluate_guess(cyphertext_bytes, key))
         .min()
         .expect("attempted to find min of empty iterator")
 }
 
-fn evaluate_guess(cyphertext_bytes: &Vec<u8>, key: &u8) -> ScoredCleartext {
-    let cleartext = xor_cypher::single_byte_encrypt(cyphertext_bytes, key);
+fn evaluate_guess(cyphertext_bytes: &[u8], key: u8) -> ScoredCleartext {
+    let cleartext = xor_cypher::single_byte_encrypt(cyphertext_bytes, key).into_vec();
     let score = score(&cleartext);
     ScoredCleartext { cleartext, score }
 }
@@ -50,7 +49,7 @@ fn evaluate_guess(cyphertext_bytes: &Vec<u8>, key: &u8) -> ScoredCleartext {
 /// if the observed frequency distribution conforms perfectly to ground truth,
 /// the difference will be 0
 /// (cf: Katz & Lindell's *Introduction To Modern Cryptography*, p. 12)
-fn score(cyphertext_bytes: &Vec<u8>) -> f64 {
+fn score(cyphertext_bytes: &[u8]) -> f64 {
     (sum_frequency_products(cyphertext_bytes) - SUMMED_SQUARED_FREQUENCIES).abs()
 }
 
@@ -59,14 +58,14 @@ fn score(cyphertext_bytes: &Vec<u8>) -> f64 {
 /// (1) the observed frequency of the ith character in the observed array
 /// (2) the ground-truth frequency of the ith character in the observed array
 /// for every byte in the array
-fn sum_frequency_products(bytes: &Vec<u8>) -> f64 {
+fn sum_frequency_products(bytes: &[u8]) -> f64 {
     let observed_freqs: HashMap<u8, f64> = calc_frequencies(bytes);
     FREQS_BY_CHAR.iter().fold(0_f64, |acc, (b, freq)| {
-        acc + (freq * observed_freqs.get(&b).unwrap_or(&0_f64))
+        acc + (freq * observed_freqs.get(b).unwrap_or(&0_f64))
     })
 }
 
-fn calc_frequencies(bytes: &Vec<u8>) -> HashMap<u8, f64> {
+fn calc_frequencies(bytes: &[u8]) -> HashMap<u8, f64> {
     let len = bytes.len() as f64;
     let mut counts = HashMap::<u8, usize>::new();
     for &b in bytes.iter() {
@@ -85,7 +84,7 @@ fn calc_frequencies(bytes: &Vec<u8>) -> HashMap<u8, f64> {
 
 /// read possibly encrypted messages from file, then detect which one
 /// is most likely to be single-byte xor encrypted based on entropy
-pub fn detect_xor_encryption_from_file(path: &Path) -> Vec<u8> {
+pub fn detect_xor_encryption_from_file(path: &Path) -> Bytes {
     let f = File::open(path).expect("failed to open file");
     let messages = BufReader::new(&f)
         .lines()
@@ -96,18 +95,20 @@ pub fn detect_xor_encryption_from_file(path: &Path) -> Vec<u8> {
 
 /// detect message with lowest entropy, where we use a high number of missing bytes
 /// as a proxy for narrow/clumped distribution of bytes & :. low entropy
-pub fn detect_xor_encryption(messages: Vec<Vec<u8>>) -> Vec<u8> {
+pub fn detect_xor_encryption<T: AsRef<[u8]>>(messages: Vec<T>) -> Bytes {
+    let first = messages[0].as_ref();
     messages[1..]
         .iter()
+        .map(AsRef::as_ref)
         .fold(
-            (&messages[0], count_missing_bytes(&messages[0])),
+            (first, count_missing_bytes(first)),
             |(curr_guess, curr_max), msg| match count_missing_bytes(msg) {
                 new_max if new_max > curr_max => (msg, new_max),
                 _ => (curr_guess, curr_max),
             },
         )
         .0
-        .to_vec()
+        .into()
 }
 
 /// count the number of byte values (ints between 0 and 255) not present in an input byte array
@@ -120,43 +121,38 @@ fn count_missing_bytes(message: &[u8]) -> usize {
 mod xor_attack_tests {
     use super::*;
     use crate::encoding;
-    use std::iter;
 
     lazy_static! {
-        static ref ENGLISH_LIKE_DISTR: Vec<u8> = {
-            iter::repeat('a')
-                .take(8)
-                .chain(iter::repeat('e').take(7))
-                .chain(iter::repeat('i').take(5))
-                .chain(iter::repeat('o').take(5))
-                .chain(iter::repeat('r').take(5))
-                .chain(iter::repeat('s').take(4))
-                .chain(iter::repeat('n').take(4))
-                .chain(iter::repeat('1').take(4))
-                .chain(iter::repeat('2').take(3))
-                .chain(iter::repeat('t').take(3))
-                .chain(iter::repeat('u').take(2))
-                .collect::<String>()
-                .as_bytes()
-                .to_vec()
-        };
-        static ref NON_ENGLISH_LIKE_DISTR: Vec<u8> = {
-            iter::repeat('&')
-                .take(8)
-                .chain(iter::repeat(':').take(7))
-                .chain(iter::repeat('|').take(5))
-                .chain(iter::repeat('>').take(5))
-                .chain(iter::repeat('<').take(5))
-                .chain(iter::repeat(';').take(4))
-                .chain(iter::repeat('?').take(4))
-                .chain(iter::repeat('ö').take(4))
-                .chain(iter::repeat('{').take(3))
-                .chain(iter::repeat('}').take(3))
-                .chain(iter::repeat('=').take(2))
-                .collect::<String>()
-                .as_bytes()
-                .to_vec()
-        };
+        static ref ENGLISH_LIKE_DISTR: Vec<u8> = [
+            "a".repeat(8),
+            "e".repeat(7),
+            "i".repeat(5),
+            "o".repeat(5),
+            "r".repeat(5),
+            "s".repeat(4),
+            "n".repeat(4),
+            "1".repeat(4),
+            "2".repeat(3),
+            "t".repeat(3),
+            "u".repeat(2)
+        ]
+        .concat()
+        .into_bytes();
+        static ref NON_ENGLISH_LIKE_DISTR: Vec<u8> = [
+            "&".repeat(8),
+            ":".repeat(7),
+            "|".repeat(5),
+            ">".repeat(5),
+            "<".repeat(5),
+            ";".repeat(4),
+            "?".repeat(4),
+            "ö".repeat(4),
+            "{".repeat(3),
+            "}".repeat(3),
+            "=".repeat(2)
+        ]
+        .concat()
+        .into_bytes();
     }
 
     /*************************************
@@ -165,9 +161,8 @@ mod xor_attack_tests {
 
     #[test]
     fn brute_force_decrpyting_xor_cypher() {
-        let cleartext = String::from("hello there world how are you.");
-        let cyphertext =
-            xor_cypher::single_byte_encrypt(&cleartext.as_bytes().to_vec(), &('a' as u8));
+        let cleartext = Bytes::from("hello there world how are you.");
+        let cyphertext = xor_cypher::single_byte_encrypt(&cleartext, b'a');
         assert_eq!(brute_force_decrypt(&cyphertext), cleartext);
     }
 
@@ -191,17 +186,17 @@ mod xor_attack_tests {
     #[test]
     fn calculating_frequencies() {
         let freqs = calc_frequencies(&ENGLISH_LIKE_DISTR);
-        assert_eq!(freqs.get(&('a' as u8)), Some(&0.16));
-        assert_eq!(freqs.get(&('e' as u8)), Some(&0.14));
-        assert_eq!(freqs.get(&('i' as u8)), Some(&0.10));
-        assert_eq!(freqs.get(&('o' as u8)), Some(&0.10));
-        assert_eq!(freqs.get(&('r' as u8)), Some(&0.10));
-        assert_eq!(freqs.get(&('s' as u8)), Some(&0.08));
-        assert_eq!(freqs.get(&('n' as u8)), Some(&0.08));
-        assert_eq!(freqs.get(&('1' as u8)), Some(&0.08));
-        assert_eq!(freqs.get(&('2' as u8)), Some(&0.06));
-        assert_eq!(freqs.get(&('t' as u8)), Some(&0.06));
-        assert_eq!(freqs.get(&('u' as u8)), Some(&0.04));
+        assert_eq!(freqs.get(&b'a'), Some(&0.16));
+        assert_eq!(freqs.get(&b'e'), Some(&0.14));
+        assert_eq!(freqs.get(&b'i'), Some(&0.10));
+        assert_eq!(freqs.get(&b'o'), Some(&0.10));
+        assert_eq!(freqs.get(&b'r'), Some(&0.10));
+        assert_eq!(freqs.get(&b's'), Some(&0.08));
+        assert_eq!(freqs.get(&b'n'), Some(&0.08));
+        assert_eq!(freqs.get(&b'1'), Some(&0.08));
+        assert_eq!(freqs.get(&b'2'), Some(&0.06));
+        assert_eq!(freqs.get(&b't'), Some(&0.06));
+        assert_eq!(freqs.get(&b'u'), Some(&0.04));
     }
 
     /*************************************
@@ -210,7 +205,7 @@ mod xor_attack_tests {
 
     #[test]
     fn detecting_xor_encryption() {
-        let possibly_encrypted_messages = vec![
+        let possibly_encrypted_messages = [
             Hex("33e80130f45708395457573406422a3b0d03e6e5053d0d2d151c083337a2".to_string()),
             Hex("551be2082b1563c4ec2247140400124d4b6508041b5a472256093aea1847".to_string()),
             Hex("7b5a4215415d544115415d5015455447414c155c46155f4058455c5b523f".to_string()),
@@ -218,7 +213,7 @@ mod xor_attack_tests {
             Hex("2d395e57143359e80efffb13330633ea19e323077b4814571e5a3de73a1f".to_string()),
         ]
         .iter()
-        .map(|hex| encoding::hex2bytes(&hex))
+        .map(encoding::hex2bytes)
         .collect::<Vec<Vec<u8>>>();
 
         assert_eq!(
@@ -231,7 +226,7 @@ mod xor_attack_tests {
     fn detecting_xor_encryption_from_file() {
         let path = Path::new("data/single_byte_xor_small_sample.txt");
         assert_eq!(
-            detect_xor_encryption_from_file(&path),
+            detect_xor_encryption_from_file(path),
             encoding::hex2bytes(&Hex(
                 "7b5a4215415d544115415d5015455447414c155c46155f4058455c5b523f".to_string()
             )),
@@ -240,9 +235,9 @@ mod xor_attack_tests {
 
     #[test]
     fn counting_missing_bytes() {
-        let bs1: &[u8] = &(0u8..=255u8).into_iter().collect::<Vec<u8>>()[..];
-        let bs2: &[u8] = &(0u8..=127u8).into_iter().collect::<Vec<u8>>()[..];
-        let bs3: &[u8] = &(0u8..=0u8).into_iter().collect::<Vec<u8>>()[..];
+        let bs1: &[u8] = &(0u8..=255u8).collect::<Vec<u8>>()[..];
+        let bs2: &[u8] = &(0u8..=127u8).collect::<Vec<u8>>()[..];
+        let bs3: &[u8] = &(0u8..=0u8).collect::<Vec<u8>>()[..];
 
         assert_eq!(count_missing_bytes(bs1), 0);
         assert_eq!(count_missing_bytes(bs2), 128);
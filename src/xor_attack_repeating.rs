@@ -1,3 +1,4 @@
+use crate::bytes::Bytes;
 use crate::scoring::{ScoredCleartext, ScoredCleartextBlocks};
 use crate::xor_attack;
 use crate::xor_cypher;
@@ -17,18 +18,18 @@ struct KeysizeDistance {
 
 /// given a `cyphertext`:
 /// 1. guess the 4 most likely repeating-key XOR keysizes by comparing the edit distance
-////   between the first 20 keysize-length blocks of the cyphertext
+///    between the first 20 keysize-length blocks of the cyphertext
 /// 2. for each likely keysize, partition the cyphertext into keysize-length blocks,
 ///    transpose the blocks into keysize-number blocks, perform brute force xor decryption
 ///    on each block (each of which will have a different key), then identify the correct cleartext
 ///    by picking the collection of transposed blocks with the lowest overall deviation
 ///    from ground-truth char frequency
 /// 3. unpartition, un-tranpose, and encode the cleartext blocks to produce the decryptd cleartext
-pub fn brute_force_decrypt(cyphertext: &[u8]) -> String {
+pub fn brute_force_decrypt<T: AsRef<[u8]>>(cyphertext: T) -> Bytes {
+    let cyphertext = cyphertext.as_ref();
     let keysizes = guess_keysizes(cyphertext);
     let transposed_cleartext_blocks = minscore_transposed_cleartext_blocks(cyphertext, keysizes);
-    let cleartext_bytes = unpartition(transpose_owned(transposed_cleartext_blocks));
-    String::from_utf8_lossy(&cleartext_bytes).to_string()
+    Bytes(unpartition(transpose_owned(transposed_cleartext_blocks)))
 }
 
 /// given a vec of N `likely_keysizes` and a `cyphertext`
@@ -52,7 +53,7 @@ fn minscore_transposed_cleartext_blocks(cyphertext: &[u8], keysizes: Vec<usize>)
 fn find_min_score_xor(transposed_cyphertext: Vec<Vec<u8>>) -> Vec<ScoredCleartext> {
     transposed_cyphertext
         .par_iter()
-        .map(|block| xor_attack::find_min_score_xor(block))
+        .map(xor_attack::find_min_score_xor)
         .collect()
 }
 
@@ -77,7 +78,7 @@ fn guess_keysizes(cyphertext: &[u8]) -> Vec<usize> {
 }
 
 /// partitions a byte array into `num_blocks` blocks of `block_size` size
-fn partition(cyphertext: &[u8], num_blocks: usize, block_size: usize) -> Vec<&[u8]> {
+pub(crate) fn partition(cyphertext: &[u8], num_blocks: usize, block_size: usize) -> Vec<&[u8]> {
     let num_blocks = truncate_num_blocks(cyphertext, num_blocks, block_size);
     (0..num_blocks)
         .into_par_iter()
@@ -113,11 +114,11 @@ fn transpose_owned(blocks: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
 }
 
 fn calc_hamming_dist_for_keysize(cyphertext: &[u8], keysize: usize) -> KeysizeDistance {
-    let blocks = partition(&cyphertext, NUM_HAMMING_DIST_SAMPLES, keysize);
+    let blocks = partition(cyphertext, NUM_HAMMING_DIST_SAMPLES, keysize);
     let dist = calc_avg_hamming_distance(&blocks);
     KeysizeDistance {
         keysize,
-        dist: dist as f32 / keysize as f32, // normalize dist measurements based on keysize size
+        dist: dist / keysize as f32, // normalize dist measurements based on keysize size
     }
 }
 
@@ -148,7 +149,7 @@ fn calc_hamming_distance(bs1: &[u8], bs2: &[u8]) -> u32 {
         .sum()
 }
 
-fn keep_n_smallest(n: usize, ksds: &mut Vec<KeysizeDistance>) -> Vec<KeysizeDistance> {
+fn keep_n_smallest(n: usize, ksds: &mut [KeysizeDistance]) -> Vec<KeysizeDistance> {
     ksds.sort_by(|a, b| a.dist.partial_cmp(&b.dist).unwrap());
     ksds.iter().take(n).cloned().collect()
 }
@@ -190,16 +191,16 @@ mod xor_attack_repeating_tests {
 
     #[test]
     fn decrypting_repeating_key_xor_encrypted_cyphertext() {
-        let cyphertext = xor_cypher::repeating_key_encrypt(&CLEARTEXT, &KEY);
+        let cyphertext = xor_cypher::repeating_key_encrypt(&*CLEARTEXT, &*KEY);
         let decrypted = brute_force_decrypt(&cyphertext);
-        assert_eq!(encoding::bytes2str(&CLEARTEXT), decrypted)
+        assert_eq!(encoding::bytes2str(&CLEARTEXT), decrypted.to_string_lossy())
     }
 
     #[test]
     fn guessing_keysize() {
         // TODO: add keys with randomized lenghts/contents (quickcheck-style)
         // to increase confidence in values of NUM_KEYSIZE_GUESSES, NUM_HAMMING_DIST_SAMPLES
-        let cyphertext = xor_cypher::repeating_key_encrypt(&CLEARTEXT, &KEY);
+        let cyphertext = xor_cypher::repeating_key_encrypt(&*CLEARTEXT, &*KEY);
         let likely_keysizes = guess_keysizes(&cyphertext);
 
         assert!(likely_keysizes.contains(&KEY.len()));
@@ -1,17 +1,19 @@
 extern crate hex;
+use crate::bytes::Bytes;
 use crate::encoding::Hex;
 use hex::FromHexError;
 
-pub fn single_byte_encrypt(bv1: &Vec<u8>, b2: &u8) -> Vec<u8> {
-    repeating_key_encrypt(bv1, &vec![*b2])
+pub fn single_byte_encrypt<T: AsRef<[u8]>>(cleartext: T, key: u8) -> Bytes {
+    repeating_key_encrypt(cleartext, [key])
 }
 
-pub fn repeating_key_encrypt(cleartext: &Vec<u8>, key: &Vec<u8>) -> Vec<u8> {
+pub fn repeating_key_encrypt<T: AsRef<[u8]>, K: AsRef<[u8]>>(cleartext: T, key: K) -> Bytes {
     cleartext
+        .as_ref()
         .iter()
-        .zip(key.iter().cycle())
+        .zip(key.as_ref().iter().cycle())
         .map(|(&a, &b)| a ^ b)
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 pub fn xor_hex(h1: Hex, h2: Hex) -> Result<Hex, FromHexError> {
@@ -21,11 +23,12 @@ pub fn xor_hex(h1: Hex, h2: Hex) -> Result<Hex, FromHexError> {
     Ok(Hex(hex::encode(bv3)))
 }
 
-pub fn xor(bs1: &[u8], bs2: &[u8]) -> Vec<u8> {
-    bs1.iter()
-        .zip(bs2.iter())
+pub fn xor<A: AsRef<[u8]>, B: AsRef<[u8]>>(bs1: A, bs2: B) -> Bytes {
+    bs1.as_ref()
+        .iter()
+        .zip(bs2.as_ref().iter())
         .map(|(&a, &b)| a ^ b)
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 #[cfg(test)]
@@ -49,7 +52,7 @@ mod xor_cypher_tests {
     #[test]
     fn test_xor() {
         assert_eq!(
-            xor(&[0b1100_0000, 0b0000_0000], &[0b0110_0000, 0b0000_0000]),
+            xor([0b1100_0000, 0b0000_0000], [0b0110_0000, 0b0000_0000]),
             vec![0b1010_0000, 0b0000_0000]
         );
     }
@@ -57,7 +60,7 @@ mod xor_cypher_tests {
     #[test]
     fn encrypting_with_single_byte_key() {
         assert_eq!(
-            single_byte_encrypt(&vec![0b1100_0000, 0b0000_0000], &0b0110_0000),
+            single_byte_encrypt([0b1100_0000, 0b0000_0000], 0b0110_0000),
             vec![0b1010_0000, 0b0110_0000]
         );
     }
@@ -74,5 +77,4 @@ mod xor_cypher_tests {
             encoding::hex2bytes(&expected_cyphertext)
         );
     }
-
 }